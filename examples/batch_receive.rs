@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorResult, ActorSelf, ActorSpec, ExitReason, Reply, Scope, ScopeContext, call,
+};
+
+enum WriteMsg {
+    Write(String),
+    Sync(Reply<()>),
+}
+
+struct BatchWriter {
+    flushes: usize,
+    written: usize,
+}
+
+#[async_trait]
+impl Actor for BatchWriter {
+    type Args = ();
+    type Msg = WriteMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self {
+            flushes: 0,
+            written: 0,
+        })
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        self.receive_batch(this, vec![msg]).await
+    }
+
+    async fn receive_batch(&mut self, _: &ActorSelf<Self>, msgs: Vec<Self::Msg>) -> ActorResult {
+        let mut lines = vec![];
+        let mut syncs = vec![];
+        for msg in msgs {
+            match msg {
+                WriteMsg::Write(line) => lines.push(line),
+                WriteMsg::Sync(reply) => syncs.push(reply),
+            }
+        }
+        self.flushes += 1;
+        self.written += lines.len();
+        for reply in syncs {
+            reply.send(())?;
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!(
+            "BatchWriter wrote {} lines in {} flushes",
+            self.written, self.flushes
+        );
+        Ok(())
+    }
+}
+
+struct DrainingWriter {
+    flushes: usize,
+    written: usize,
+}
+
+#[async_trait]
+impl Actor for DrainingWriter {
+    type Args = ();
+    type Msg = WriteMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self {
+            flushes: 0,
+            written: 0,
+        })
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        let mut pending = vec![msg];
+        pending.extend(this.drain());
+        let mut syncs = vec![];
+        for msg in pending {
+            match msg {
+                WriteMsg::Write(_) => self.written += 1,
+                WriteMsg::Sync(reply) => syncs.push(reply),
+            }
+        }
+        self.flushes += 1;
+        for reply in syncs {
+            reply.send(())?;
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!(
+            "DrainingWriter wrote {} lines in {} flushes",
+            self.written, self.flushes
+        );
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let spec = ActorSpec::new().bounded(1000).batch(256);
+    let batch = scope.spawn_with::<BatchWriter>(spec, ()).await;
+
+    let spec = ActorSpec::new().bounded(1000);
+    let draining = scope.spawn_with::<DrainingWriter>(spec, ()).await;
+
+    for i in 0..1000 {
+        batch.cast(WriteMsg::Write(format!("line {i}"))).await?;
+        draining.cast(WriteMsg::Write(format!("line {i}"))).await?;
+    }
+    call!(batch, WriteMsg::Sync).await?;
+    call!(draining, WriteMsg::Sync).await?;
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
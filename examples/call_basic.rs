@@ -1,7 +1,7 @@
 use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
-use xacto::{Act, Actor, ActorResult, ActorSelf, Reply, Scope, ScopeContext, call};
+use xacto::{Act, Actor, ActorResult, ActorSelf, ExitReason, Reply, Scope, ScopeContext, call};
 
 enum TimeServiceMsg {
     GetTime(Reply<Duration>),
@@ -36,7 +36,7 @@ impl Actor for TimeService {
         Ok(())
     }
 
-    async fn exit(&mut self) -> ActorResult {
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
         println!("TimeService exiting");
         Ok(())
     }
@@ -51,7 +51,12 @@ impl Actor for Client {
 
     async fn start(this: &ActorSelf<Self>, svc: Self::Args) -> ActorResult<Self> {
         for _ in 0..10 {
-            let time = call!(svc, TimeServiceMsg::GetTime).await?;
+            let time = call!(
+                svc,
+                timeout = Duration::from_secs(1),
+                TimeServiceMsg::GetTime
+            )
+            .await?;
             println!("Received time: {}ms", time.as_millis());
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
@@ -67,7 +72,7 @@ impl Actor for Client {
         Ok(())
     }
 
-    async fn exit(&mut self) -> ActorResult {
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
         println!("TimeService exiting");
         Ok(())
     }
@@ -1,6 +1,5 @@
 use async_trait::async_trait;
-use tokio::sync::oneshot;
-use xacto::{Actor, ActorError, ActorResult, ActorSelf, Reply};
+use xacto::{Actor, ActorError, ActorResult, ActorSelf, ExitReason, Reply};
 use xacto_derive::RpcMessage;
 
 struct MyActor {
@@ -17,6 +16,7 @@ enum MyActorMsg {
     GetCount3(String, i32, Reply<String>),
 }
 
+#[allow(dead_code)]
 #[derive(Debug, RpcMessage)]
 enum AnotherMsg {
     DoSomething(String),
@@ -62,7 +62,7 @@ impl Actor for MyActor {
         Ok(())
     }
 
-    async fn exit(&mut self) -> ActorResult {
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
         println!("MyActor exiting");
         Ok(())
     }
@@ -85,7 +85,7 @@ async fn main() -> Result<(), ActorError> {
         (rx, serde_json::to_string(&env).unwrap())
     };
 
-    println!("json1: {}", &json1);
+    println!("json1: {}", json1);
 
     let json2 = {
         let act_clone = act.clone();
@@ -98,7 +98,7 @@ async fn main() -> Result<(), ActorError> {
         serde_json::to_string(&res).unwrap()
     };
 
-    println!("json2: {}", &json2);
+    println!("json2: {}", json2);
 
     {
         let env = serde_json::from_str(&json2).unwrap();
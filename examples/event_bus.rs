@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorResult, ActorSelf, ActorSpec, EventBus, ExitReason, Scope, ScopeContext,
+    SubscriberPolicy, Subscription,
+};
+
+#[derive(Debug, Clone)]
+enum OrderEvent {
+    Placed(u32),
+    Shipped(u32),
+}
+
+enum AuditMsg {
+    Event(OrderEvent),
+}
+
+impl From<OrderEvent> for AuditMsg {
+    fn from(event: OrderEvent) -> Self {
+        AuditMsg::Event(event)
+    }
+}
+
+struct Auditor;
+
+#[async_trait]
+impl Actor for Auditor {
+    type Args = ();
+    type Msg = AuditMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            AuditMsg::Event(OrderEvent::Placed(order)) => println!("audit: placed #{order}"),
+            AuditMsg::Event(OrderEvent::Shipped(order)) => println!("audit: shipped #{order}"),
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+// A slow subscriber that would rather miss notifications than hold up the bus
+struct Mailer;
+
+#[async_trait]
+impl Actor for Mailer {
+    type Args = ();
+    type Msg = OrderEvent;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, event: Self::Msg) -> ActorResult {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        println!("mailer: {event:?}");
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let bus = scope.spawn::<EventBus<OrderEvent>>(()).await;
+    let auditor = scope.spawn::<Auditor>(()).await;
+    let mailer = scope
+        .spawn_with::<Mailer>(ActorSpec::new().bounded(1), ())
+        .await;
+
+    bus.subscribe(auditor.clone(), |_| true).await?;
+    let mailer_subscription = Subscription::new(mailer)
+        .filter(|event| matches!(event, OrderEvent::Shipped(_)))
+        .policy(SubscriberPolicy::Drop);
+    bus.subscribe_with(mailer_subscription).await?;
+
+    for order in 0..5 {
+        bus.publish(OrderEvent::Placed(order)).await?;
+        bus.publish(OrderEvent::Shipped(order)).await?;
+    }
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // The auditor is dropped from the bus on the next publish after it stops
+    scope.exit_actor_and_wait(auditor.id()).await;
+    bus.publish(OrderEvent::Placed(5)).await?;
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
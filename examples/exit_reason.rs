@@ -0,0 +1,75 @@
+use std::fmt;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext};
+
+#[derive(Debug)]
+struct QuotaExceeded;
+
+impl fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "quota exceeded")
+    }
+}
+
+impl std::error::Error for QuotaExceeded {}
+
+enum JobMsg {
+    Done,
+    OverQuota,
+    Kill,
+}
+
+struct Job;
+
+#[async_trait]
+impl Actor for Job {
+    type Args = ();
+    type Msg = JobMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            JobMsg::Done => this.exit(),
+            JobMsg::OverQuota => this.exit_with(ExitReason::custom(QuotaExceeded)),
+            JobMsg::Kill => this.exit_with(ExitReason::Killed),
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, reason: &ExitReason) -> ActorResult {
+        println!("Job cleaning up after {reason}");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let done = scope.spawn::<Job>(()).await;
+    let over_quota = scope.spawn::<Job>(()).await;
+    let killed = scope.spawn::<Job>(()).await;
+    let _shutdown = scope.spawn::<Job>(()).await;
+
+    done.cast(JobMsg::Done).await?;
+    over_quota.cast(JobMsg::OverQuota).await?;
+    killed.cast(JobMsg::Kill).await?;
+
+    for _ in 0..3 {
+        if let Some((id, output)) = scope.next_finished().await {
+            println!("Finished: {id:?}, {output:?}");
+        }
+    }
+
+    scope.exit_all().await;
+    while let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {id:?}, {output:?}");
+    }
+
+    Ok(())
+}
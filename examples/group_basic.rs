@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Reply, Scope, ScopeContext};
+
+#[derive(Clone)]
+struct Invalidate(String);
+
+enum CacheMsg {
+    Put(String, String),
+    Get(String, Reply<Option<String>>),
+    Invalidate(Invalidate),
+}
+
+impl From<Invalidate> for CacheMsg {
+    fn from(msg: Invalidate) -> Self {
+        CacheMsg::Invalidate(msg)
+    }
+}
+
+struct Cache {
+    name: &'static str,
+    entries: HashMap<String, String>,
+}
+
+#[async_trait]
+impl Actor for Cache {
+    type Args = &'static str;
+    type Msg = CacheMsg;
+
+    async fn start(this: &ActorSelf<Self>, name: Self::Args) -> ActorResult<Self> {
+        this.join("cache-invalidation")?;
+        Ok(Self {
+            name,
+            entries: HashMap::new(),
+        })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            CacheMsg::Put(key, value) => {
+                self.entries.insert(key, value);
+            }
+            CacheMsg::Get(key, reply) => {
+                println!("{} serving {key:?}", self.name);
+                reply.send(self.entries.get(&key).cloned())?;
+            }
+            CacheMsg::Invalidate(Invalidate(key)) => {
+                println!("{} invalidating {key:?}", self.name);
+                self.entries.remove(&key);
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let mut caches = vec![];
+    for name in ["cache-a", "cache-b", "cache-c"] {
+        let cache = scope.spawn::<Cache>(name).await;
+        scope.wait_started(cache.id()).await;
+        cache
+            .cast(CacheMsg::Put("user:1".into(), "alice".into()))
+            .await?;
+        caches.push(cache);
+    }
+
+    let members = scope.members::<CacheMsg>("cache-invalidation")?;
+    for _ in 0..3 {
+        let members = scope.members::<CacheMsg>("cache-invalidation")?;
+        let value = members
+            .call_any(|reply| CacheMsg::Get("user:1".into(), reply))
+            .await?;
+        println!("got {value:?}");
+    }
+
+    let sent = members.broadcast(Invalidate("user:1".into())).await;
+    println!("invalidated {sent} caches");
+    for cache in &caches {
+        let value = cache
+            .call(|reply| CacheMsg::Get("user:1".into(), reply))
+            .await?;
+        println!("got {value:?}");
+    }
+
+    // Stopped members leave the group
+    scope.exit_actor_and_wait(caches[0].id()).await;
+    let members = scope.members::<CacheMsg>("cache-invalidation")?;
+    println!("{} members left", members.len());
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ErrorAction, ExitReason, Reply, Scope, ScopeContext,
+    call,
+};
+
+#[derive(Debug)]
+struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "can't parse {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+struct Desync;
+
+impl std::fmt::Display for Desync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "connection out of sync")
+    }
+}
+
+impl std::error::Error for Desync {}
+
+enum ConnMsg {
+    Line(String),
+    Total(Reply<i64>),
+}
+
+struct Connection {
+    incarnation: usize,
+    total: i64,
+}
+
+#[async_trait]
+impl Actor for Connection {
+    // (incarnation, total carried over from the previous incarnation)
+    type Args = (usize, i64);
+    type Msg = ConnMsg;
+
+    async fn start(_: &ActorSelf<Self>, (incarnation, total): Self::Args) -> ActorResult<Self> {
+        println!("Connection #{incarnation} started with total {total}");
+        Ok(Self { incarnation, total })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            ConnMsg::Line(line) if line == "desync" => Err(Desync.into()),
+            ConnMsg::Line(line) => {
+                self.total += line.parse::<i64>().map_err(|_| ParseError(line))?;
+                Ok(())
+            }
+            ConnMsg::Total(reply) => Ok(reply.send(self.total)?),
+        }
+    }
+
+    async fn on_receive_error(&mut self, _: &ActorSelf<Self>, error: ActorError) -> ErrorAction {
+        println!("Connection #{} error: {error}", self.incarnation);
+        match &error {
+            ActorError::Unknown(e) if e.is::<ParseError>() => ErrorAction::Resume,
+            ActorError::Unknown(e) if e.is::<Desync>() => ErrorAction::Restart(error),
+            _ => ErrorAction::Stop(error),
+        }
+    }
+
+    async fn pre_restart(&mut self, _: &ActorSelf<Self>, _: ActorError) -> ActorResult<Self::Args> {
+        Ok((self.incarnation + 1, self.total))
+    }
+
+    async fn exit(&mut self, reason: &ExitReason) -> ActorResult {
+        println!("Connection #{} exiting: {reason}", self.incarnation);
+        Ok(())
+    }
+
+    async fn post_stop(&mut self, _: &ActorSelf<Self>) {
+        println!("Connection #{} stopped", self.incarnation);
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let conn = scope.spawn::<Connection>((1, 0)).await;
+
+    for line in ["1", "2", "three", "desync", "4"] {
+        conn.cast(ConnMsg::Line(line.to_string())).await?;
+    }
+    println!("Total: {}", call!(conn, ConnMsg::Total).await?);
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
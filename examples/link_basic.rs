@@ -0,0 +1,90 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, ExitSignal, Scope, ScopeContext,
+};
+
+enum StageMsg {
+    Crash,
+}
+
+struct Stage {
+    name: &'static str,
+}
+
+#[async_trait]
+impl Actor for Stage {
+    type Args = &'static str;
+    type Msg = StageMsg;
+
+    async fn start(_: &ActorSelf<Self>, name: Self::Args) -> ActorResult<Self> {
+        Ok(Self { name })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            StageMsg::Crash => {
+                println!("Stage {} crashing", self.name);
+                Err(ActorError::HandleClosed)
+            }
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Stage {} exiting", self.name);
+        Ok(())
+    }
+}
+
+struct Observer;
+
+#[async_trait]
+impl Actor for Observer {
+    type Args = ();
+    type Msg = ();
+
+    async fn start(this: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        this.trap_exit(true);
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn handle_exit(&mut self, this: &ActorSelf<Self>, exit: ExitSignal) -> ActorResult {
+        println!(
+            "Observer trapped exit from {:?}: {:?}",
+            exit.from, exit.reason
+        );
+        this.exit();
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Observer exiting");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let source = scope.spawn::<Stage>("source").await;
+    let transform = scope.spawn::<Stage>("transform").await;
+    let sink = scope.spawn::<Stage>("sink").await;
+    let observer = scope.spawn::<Observer>(()).await;
+
+    scope.link(source.id(), transform.id())?;
+    scope.link(transform.id(), sink.id())?;
+    scope.link(sink.id(), observer.id())?;
+
+    transform.cast(StageMsg::Crash).await?;
+
+    while let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {:?}, {:?}", id, output);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Act, Actor, ActorResult, ActorSelf, ActorSpec, ExitReason, Overflow, Scope, ScopeContext,
+};
+
+struct Slow {
+    name: &'static str,
+    received: Vec<u32>,
+}
+
+#[async_trait]
+impl Actor for Slow {
+    type Args = &'static str;
+    type Msg = u32;
+
+    async fn start(_: &ActorSelf<Self>, name: Self::Args) -> ActorResult<Self> {
+        Ok(Self {
+            name,
+            received: vec![],
+        })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        self.received.push(msg);
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("{} received {:?}", self.name, self.received);
+        Ok(())
+    }
+}
+
+async fn flood(act: &Act<u32>) {
+    for i in 0..10 {
+        if let Err(e) = act.cast(i).await {
+            println!("{:?} rejected: {:?}", act, e);
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let policies = [
+        ("block", Overflow::Block),
+        ("fail", Overflow::Fail),
+        ("drop_newest", Overflow::DropNewest),
+        ("drop_oldest", Overflow::DropOldest),
+    ];
+
+    for (name, overflow) in policies {
+        let spec = ActorSpec::new().bounded(3).overflow(overflow);
+        let act = scope.spawn_with::<Slow>(spec, name).await;
+        scope.wait_started(act.id()).await;
+        flood(&act).await;
+    }
+
+    let unbounded = scope
+        .spawn_with::<Slow>(ActorSpec::new().unbounded(), "unbounded")
+        .await;
+    flood(&unbounded).await;
+
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    scope.exit_and_wait().await;
+}
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use xacto::{
+    Act, Actor, ActorError, ActorId, ActorResult, ActorSelf, Down, ExitReason, Scope, ScopeContext,
+};
+
+enum WorkerMsg {
+    Crash,
+}
+
+struct Worker;
+
+#[async_trait]
+impl Actor for Worker {
+    type Args = ();
+    type Msg = WorkerMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            WorkerMsg::Crash => Err(ActorError::HandleClosed),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+struct Watcher {
+    remaining: usize,
+}
+
+#[async_trait]
+impl Actor for Watcher {
+    type Args = usize;
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, remaining: Self::Args) -> ActorResult<Self> {
+        Ok(Self { remaining })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn handle_down(&mut self, this: &ActorSelf<Self>, down: Down) -> ActorResult {
+        println!("Down: {:?} {:?}", down.id, down.reason);
+        self.remaining -= 1;
+        if self.remaining == 0 {
+            this.exit();
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Watcher exiting");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut workers = Scope::new(context.clone());
+    let mut watchers = Scope::new(context);
+
+    let worker: Act<WorkerMsg> = workers.spawn::<Worker>(()).await;
+    let never_spawned = ActorId(workers.id(), 100);
+
+    let watcher = watchers.spawn::<Watcher>(2).await;
+    watchers.monitor(watcher.id(), worker.id());
+    watchers.monitor(watcher.id(), never_spawned);
+
+    worker.cast(WorkerMsg::Crash).await?;
+
+    while let Some((id, output)) = watchers.next_finished().await {
+        println!("Finished: {:?}, {:?}", id, output);
+    }
+
+    workers.exit_and_wait().await;
+
+    Ok(())
+}
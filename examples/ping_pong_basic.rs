@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use xacto::{Act, Actor, ActorResult, ActorSelf, Scope, ScopeContext};
+use xacto::{Act, Actor, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext};
 
 #[derive(Debug)]
 enum Msg {
@@ -25,7 +25,9 @@ impl Actor for Ping {
     async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
         println!("Actor {} received message {:?}", self.id, &msg);
 
-        let act = this.act().clone();
+        let Some(act) = this.act() else {
+            return Ok(());
+        };
         let (from, reply) = match msg {
             Msg::Ping(from) => (from, Msg::Pong(act)),
             Msg::Pong(from) => (from, Msg::Ping(act)),
@@ -44,7 +46,7 @@ impl Actor for Ping {
         Ok(())
     }
 
-    async fn exit(&mut self) -> ActorResult {
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
         println!("Actor {} will exit", self.id);
         Ok(())
     }
@@ -0,0 +1,90 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorPool, ActorResult, ActorSelf, ExitReason, Reply, Routing, Scope,
+    ScopeContext,
+};
+
+enum ParseMsg {
+    // (tenant, document)
+    Parse(String, String, Reply<(usize, usize)>),
+    Crash,
+}
+
+struct Parser {
+    index: usize,
+}
+
+#[async_trait]
+impl Actor for Parser {
+    type Args = usize;
+    type Msg = ParseMsg;
+
+    async fn start(_: &ActorSelf<Self>, index: Self::Args) -> ActorResult<Self> {
+        println!("parser {index} started");
+        Ok(Self { index })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            ParseMsg::Parse(_, document, reply) => {
+                let words = document.split_whitespace().count();
+                reply.send((self.index, words))?;
+                Ok(())
+            }
+            ParseMsg::Crash => Err(ActorError::HandleClosed),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let pool = scope
+        .start_pool(ActorPool::<Parser>::new(3, |index| index))
+        .await;
+    for i in 0..4 {
+        let document = format!("round robin document {i}");
+        let (worker, words) = pool
+            .call(|reply| ParseMsg::Parse("any".into(), document, reply))
+            .await?;
+        println!("round robin: worker {worker} counted {words} words");
+    }
+
+    // The crashed worker is replaced in the same slot
+    pool.cast(ParseMsg::Crash).await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let routing = Routing::consistent_hash(|msg: &ParseMsg| match msg {
+        ParseMsg::Parse(tenant, ..) => Some(tenant.clone()),
+        ParseMsg::Crash => None,
+    });
+    let hashed = scope
+        .start_pool(ActorPool::<Parser>::new(4, |index| index).routing(routing))
+        .await;
+    for tenant in ["acme", "globex", "acme", "initech", "globex"] {
+        let (worker, _) = hashed
+            .call(|reply| ParseMsg::Parse(tenant.into(), "hello".into(), reply))
+            .await?;
+        println!("consistent hash: {tenant} -> worker {worker}");
+    }
+
+    let least_loaded = scope
+        .start_pool(ActorPool::<Parser>::new(2, |index| index).routing(Routing::LeastLoaded))
+        .await;
+    let (worker, _) = least_loaded
+        .call(|reply| ParseMsg::Parse("any".into(), "hello".into(), reply))
+        .await?;
+    println!("least loaded: worker {worker}");
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Recipient, Reply, Scope, ScopeContext};
+
+#[derive(Debug)]
+struct LogLine(&'static str, String);
+
+enum LoggerMsg {
+    Line(LogLine),
+    Count(Reply<usize>),
+}
+
+struct Logger {
+    lines: usize,
+}
+
+#[async_trait]
+impl Actor for Logger {
+    type Args = ();
+    type Msg = LoggerMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { lines: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            LoggerMsg::Line(LogLine(source, line)) => {
+                self.lines += 1;
+                println!("[{source}] {line}");
+            }
+            LoggerMsg::Count(reply) => reply.send(self.lines)?,
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+enum UploadMsg {
+    Upload(String, Reply<()>),
+}
+
+// Only knows how to send `LogLine`s, not the rest of the logger's messages
+struct Uploader {
+    log: Recipient<LogLine>,
+}
+
+#[async_trait]
+impl Actor for Uploader {
+    type Args = Recipient<LogLine>;
+    type Msg = UploadMsg;
+
+    async fn start(_: &ActorSelf<Self>, log: Self::Args) -> ActorResult<Self> {
+        Ok(Self { log })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            UploadMsg::Upload(file, reply) => {
+                let line = LogLine("uploader", format!("uploaded {file}"));
+                if let Err(e) = self.log.cast(line).await {
+                    println!("log line not delivered: {e:?}");
+                }
+                reply.send(())?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let logger = scope.spawn::<Logger>(()).await;
+    let uploader = scope
+        .spawn::<Uploader>(logger.contramap(LoggerMsg::Line))
+        .await;
+
+    for file in ["a.txt", "b.txt"] {
+        uploader
+            .call(|reply| UploadMsg::Upload(file.into(), reply))
+            .await?;
+    }
+
+    let count = logger
+        .contramap(LoggerMsg::Count)
+        .call(|reply| reply)
+        .await?;
+    println!("logged {count} lines");
+
+    // A failed send hands back the original `LogLine`
+    let log = logger.contramap(LoggerMsg::Line);
+    scope.exit_actor_and_wait(logger.id()).await;
+    if let Err(e) = log.cast(LogLine("main", "too late".into())).await {
+        println!("not delivered: {e:?}");
+    }
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Reply, Scope, ScopeContext, call};
+
+enum LoggerMsg {
+    Log(String),
+    Count(Reply<usize>),
+}
+
+struct Logger {
+    lines: usize,
+}
+
+#[async_trait]
+impl Actor for Logger {
+    type Args = ();
+    type Msg = LoggerMsg;
+
+    async fn start(this: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        this.register("logger")?;
+        Ok(Self { lines: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            LoggerMsg::Log(line) => {
+                println!("log: {line}");
+                self.lines += 1;
+            }
+            LoggerMsg::Count(reply) => reply.send(self.lines)?,
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+struct Client;
+
+#[async_trait]
+impl Actor for Client {
+    type Args = ();
+    type Msg = (String, Reply<()>);
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, (line, reply): Self::Msg) -> ActorResult {
+        if let Some(logger) = this.whereis::<LoggerMsg>("logger")? {
+            logger.cast(LoggerMsg::Log(line)).await?;
+        }
+        reply.send(())?;
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let logger = scope.spawn::<Logger>(()).await;
+    scope.wait_started(logger.id()).await;
+
+    let client = scope.spawn::<Client>(()).await;
+    for line in ["hello", "world"] {
+        client.call(|reply| (line.to_string(), reply)).await?;
+    }
+
+    if let Err(e) = scope.register("logger", client.clone()) {
+        println!("register failed: {e}");
+    }
+    if let Err(e) = scope.whereis::<(String, Reply<()>)>("logger") {
+        println!("whereis failed: {e}");
+    }
+
+    let logger = scope.whereis::<LoggerMsg>("logger")?.unwrap();
+    println!("logged {} lines", call!(logger, LoggerMsg::Count).await?);
+
+    scope.exit_actor_and_wait(logger.id()).await;
+    println!(
+        "logger after exit: {:?}",
+        scope.whereis::<LoggerMsg>("logger")?
+    );
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use xacto::{RecvError, Reply, ReplyMap};
+use xacto_derive::RpcMessage;
+
+#[allow(dead_code)]
+#[derive(Debug, RpcMessage)]
+enum PeerMsg {
+    Lookup(String, Reply<Option<u64>>),
+    Ping(Reply<()>),
+}
+
+#[tokio::main]
+async fn main() {
+    let mut replies = ReplyMap::new();
+
+    // Never answered by the peer
+    let (lookup, env) = replies.create_request_timeout(Duration::from_millis(50), |reply| {
+        PeerMsg::Lookup("alice".into(), reply)
+    });
+    println!("sent {}", serde_json::to_string(&env).unwrap());
+
+    let (ping, env) = replies.create_request(PeerMsg::Ping);
+    println!("sent {}", serde_json::to_string(&env).unwrap());
+
+    // A background task would sleep until `next_deadline` instead
+    if let Some(deadline) = replies.next_deadline() {
+        tokio::time::sleep_until(deadline).await;
+    }
+    let expired = replies.sweep_expired(tokio::time::Instant::now());
+    println!(
+        "expired {expired} requests, {} still pending",
+        replies.len()
+    );
+    println!("lookup: {:?}", lookup.await);
+
+    // The connection to the peer drops
+    replies.fail_all(RecvError::Closed);
+    println!("ping: {:?}, {} pending", ping.await, replies.len());
+}
@@ -0,0 +1,118 @@
+use std::io;
+
+use async_trait::async_trait;
+use serde::{Serialize, de::DeserializeOwned};
+use xacto::{
+    Actor, ActorResult, ActorSelf, Codec, CodecKind, ExitReason, Reply, ReplyMap, RpcClient,
+    RpcMessage, RpcServer, Scope, ScopeContext,
+};
+use xacto_derive::RpcMessage;
+
+struct Store {
+    readings: Vec<f64>,
+}
+
+#[derive(Debug, RpcMessage)]
+enum StoreMsg {
+    Record(String, Vec<f64>),
+    Mean(Reply<Option<f64>>),
+}
+
+#[async_trait]
+impl Actor for Store {
+    type Args = ();
+    type Msg = StoreMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { readings: vec![] })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            StoreMsg::Record(_, readings) => self.readings.extend(readings),
+            StoreMsg::Mean(reply) => {
+                let mean = (!self.readings.is_empty())
+                    .then(|| self.readings.iter().sum::<f64>() / self.readings.len() as f64);
+                reply.send(mean)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+// Any other format can be plugged in by implementing `Codec`
+#[derive(Debug, Clone, Copy)]
+struct PrettyJson;
+
+impl Codec for PrettyJson {
+    fn id(&self) -> u8 {
+        100
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        serde_json::to_vec_pretty(value).map_err(io::Error::other)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        serde_json::from_slice(bytes).map_err(io::Error::other)
+    }
+}
+
+#[tokio::main]
+async fn main() -> io::Result<()> {
+    let readings = (1..=32).map(|i| (i as f64).sqrt()).collect::<Vec<_>>();
+
+    // Size of the same request under every codec
+    let env =
+        StoreMsg::Record("sensor-1".into(), readings.clone()).into_request(&mut ReplyMap::new());
+    for codec in CodecKind::ALL {
+        println!("{codec:?}: {} bytes", codec.encode(&env)?.len());
+    }
+
+    let mut scope = Scope::new(ScopeContext::new());
+    let store = scope.spawn::<Store>(()).await;
+
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.serve(store.clone()));
+
+    // Each connection uses the codec its client prefers
+    for codec in CodecKind::ALL {
+        let client = RpcClient::with_codecs(connector.connect()?, &[*codec]).await?;
+        client
+            .cast(StoreMsg::Record("sensor-1".into(), readings.clone()))
+            .await
+            .unwrap();
+        let mean = client.call(StoreMsg::Mean).await.unwrap();
+        println!("{:?} client: mean {mean:?}", client.codec());
+    }
+
+    let client = RpcClient::<StoreMsg>::connect_memory(&connector).await?;
+    println!("default client negotiated {:?}", client.codec());
+    server.abort();
+
+    // A server can restrict the codecs it accepts
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.codecs(&[CodecKind::Json]).serve(store.clone()));
+
+    let client =
+        RpcClient::<StoreMsg>::with_codecs(connector.connect()?, &[CodecKind::Bincode]).await;
+    println!("bincode only: {:?}", client.err());
+
+    server.abort();
+
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.codecs(&[PrettyJson]).serve(store));
+
+    let client = RpcClient::<StoreMsg, _>::with_codecs(connector.connect()?, &[PrettyJson]).await?;
+    let mean = client.call(StoreMsg::Mean).await.unwrap();
+    println!("{:?} client: mean {mean:?}", client.codec());
+
+    server.abort();
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, Reply, RpcClient, RpcServer, Scope,
+    ScopeContext,
+};
+use xacto_derive::RpcMessage;
+
+struct Counter {
+    count: u64,
+}
+
+#[derive(Debug, RpcMessage)]
+enum CounterMsg {
+    Add(u64),
+    Get(Reply<u64>),
+    // Answered after a delay, without holding up the counter
+    SlowGet(u64, Reply<u64>),
+}
+
+#[async_trait]
+impl Actor for Counter {
+    type Args = ();
+    type Msg = CounterMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { count: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            CounterMsg::Add(n) => self.count += n,
+            CounterMsg::Get(reply) => reply.send(self.count)?,
+            CounterMsg::SlowGet(ms, reply) => {
+                let count = self.count;
+                tokio::spawn(async move {
+                    tokio::time::sleep(Duration::from_millis(ms)).await;
+                    let _ = reply.send(count);
+                });
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let mut scope = Scope::new(ScopeContext::new());
+    let counter = scope.spawn::<Counter>(()).await;
+
+    let server = RpcServer::bind("127.0.0.1:0").await?;
+    let addr = server.local_addr()?;
+    let server = tokio::spawn(server.serve(counter));
+
+    let client = RpcClient::<CounterMsg>::connect(addr).await?;
+
+    // Casts from one client arrive in the order they were sent
+    for n in 1..=10 {
+        client.cast(CounterMsg::Add(n)).await.unwrap();
+    }
+    println!("count: {:?}", client.call(CounterMsg::Get).await);
+
+    // The quick call is answered while the slow one is still in flight
+    let slow = client.call(|reply| CounterMsg::SlowGet(200, reply));
+    let quick = async {
+        let count = client.call(CounterMsg::Get).await;
+        println!("quick call answered first: {count:?}");
+    };
+    let (slow, _) = tokio::join!(slow, quick);
+    println!("slow call: {slow:?}");
+
+    let timeout = client
+        .call_timeout(Duration::from_millis(50), |reply| {
+            CounterMsg::SlowGet(200, reply)
+        })
+        .await;
+    println!("timed out: {timeout:?}");
+
+    // Stopping the server closes the connection, failing the call in flight
+    let pending = client.call(|reply| CounterMsg::SlowGet(200, reply));
+    let stop = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        server.abort();
+    };
+    let (pending, _) = tokio::join!(pending, stop);
+    println!("after disconnect: {pending:?}");
+    println!("client closed: {}", client.is_closed());
+    let closed = client.call(CounterMsg::Get).await.map_err(ActorError::from);
+    println!("call on closed client: {closed:?}");
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
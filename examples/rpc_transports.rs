@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use xacto::{
+    Act, Actor, ActorResult, ActorSelf, ExitReason, Reply, RpcClient, RpcServer, Scope,
+    ScopeContext,
+};
+use xacto_derive::RpcMessage;
+
+struct Greeter {
+    greeted: usize,
+}
+
+#[derive(Debug, RpcMessage)]
+enum GreeterMsg {
+    Greet(String, Reply<String>),
+    Count(Reply<usize>),
+}
+
+#[async_trait]
+impl Actor for Greeter {
+    type Args = ();
+    type Msg = GreeterMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { greeted: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            GreeterMsg::Greet(name, reply) => {
+                self.greeted += 1;
+                reply.send(format!("hello, {name}"))?;
+            }
+            GreeterMsg::Count(reply) => reply.send(self.greeted)?,
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+// The same client code runs over every transport
+async fn greet(transport: &str, client: RpcClient<GreeterMsg>) {
+    let greeting = client
+        .call(|reply| GreeterMsg::Greet(transport.into(), reply))
+        .await
+        .unwrap();
+    let count = client.call(GreeterMsg::Count).await.unwrap();
+    println!("{transport}: {greeting:?}, greeted {count}");
+}
+
+#[cfg(unix)]
+async fn unix(greeter: Act<GreeterMsg>) -> std::io::Result<()> {
+    let path = std::env::temp_dir().join(format!("xacto-rpc-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let server = RpcServer::bind_unix(&path)?;
+    let server = tokio::spawn(server.serve(greeter));
+
+    greet("unix", RpcClient::connect_unix(&path).await?).await;
+
+    server.abort();
+    std::fs::remove_file(&path)
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let mut scope = Scope::new(ScopeContext::new());
+    let greeter = scope.spawn::<Greeter>(()).await;
+
+    #[cfg(unix)]
+    unix(greeter.clone()).await?;
+
+    // No sockets at all, e.g. for tests
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.serve(greeter));
+
+    greet("memory", RpcClient::connect_memory(&connector).await?).await;
+    greet("memory", RpcClient::connect_memory(&connector).await?).await;
+
+    server.abort();
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorResult, ActorSelf, ExitReason, Link, LinkPublisher, Reply, Scope, ScopeContext,
+};
+
+enum DbMsg {
+    Write(String, Reply<()>),
+}
+
+struct DbWriter {
+    rows: usize,
+}
+
+#[async_trait]
+impl Actor for DbWriter {
+    type Args = ();
+    type Msg = DbMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { rows: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            DbMsg::Write(row, reply) => {
+                println!("DbWriter wrote {row:?}");
+                self.rows += 1;
+                reply.send(())?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("DbWriter exiting after {} rows", self.rows);
+        Ok(())
+    }
+}
+
+struct Flusher {
+    name: &'static str,
+    db: Link<DbMsg>,
+}
+
+#[async_trait]
+impl Actor for Flusher {
+    type Args = (&'static str, Link<DbMsg>);
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, (name, db): Self::Args) -> ActorResult<Self> {
+        Ok(Self { name, db })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    // The writer is still running, because it is only asked to exit once every flusher is done
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        let row = format!("{} buffer", self.name);
+        self.db.call(|reply| DbMsg::Write(row, reply)).await?;
+        println!("Flusher {} exiting", self.name);
+        Ok(())
+    }
+}
+
+struct Metrics;
+
+#[async_trait]
+impl Actor for Metrics {
+    type Args = ();
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Metrics exiting");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    // Spawned first, so stopped last
+    let _metrics = scope.spawn::<Metrics>(()).await;
+
+    let mut publishers = vec![];
+    let mut flushers = vec![];
+    for name in ["a", "b"] {
+        let (publisher, link) = LinkPublisher::new();
+        flushers.push(scope.spawn::<Flusher>((name, link)).await);
+        publishers.push(publisher);
+    }
+
+    // Spawned after the flushers, but stopped after them too because they depend on it
+    let db = scope.spawn::<DbWriter>(()).await;
+    for flusher in &flushers {
+        scope.depends_on(flusher.id(), db.id());
+    }
+    for publisher in &publishers {
+        publisher.update(db.clone());
+    }
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
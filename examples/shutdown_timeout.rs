@@ -0,0 +1,59 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorError, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext};
+
+#[derive(Debug, Clone, Copy)]
+enum OnExit {
+    Flush,
+    Fail,
+    Hang,
+}
+
+struct Worker {
+    on_exit: OnExit,
+}
+
+#[async_trait]
+impl Actor for Worker {
+    type Args = OnExit;
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, on_exit: Self::Args) -> ActorResult<Self> {
+        Ok(Self { on_exit })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Worker exiting: {:?}", self.on_exit);
+        match self.on_exit {
+            OnExit::Flush => {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                Ok(())
+            }
+            OnExit::Fail => Err(ActorError::HandleClosed),
+            OnExit::Hang => std::future::pending().await,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    // Stopped in reverse spawn order, so the hanging worker is asked to exit last
+    let mut workers = vec![];
+    for on_exit in [OnExit::Hang, OnExit::Fail, OnExit::Flush] {
+        workers.push(scope.spawn::<Worker>(on_exit).await);
+    }
+
+    // e.g. the grace period between SIGTERM and SIGKILL
+    let report = scope.exit_and_wait_timeout(Duration::from_secs(1)).await;
+    println!("{report:?}, clean: {}", report.is_clean());
+
+    Ok(())
+}
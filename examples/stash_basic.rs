@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ActorSpec, ExitReason, Reply, Scope, ScopeContext,
+};
+
+#[derive(Debug)]
+enum ConnMsg {
+    Connected,
+    Send(String, Reply<usize>),
+}
+
+// Queries sent before the connection is up are stashed and replayed once it is
+struct Connection {
+    connected: bool,
+    sent: usize,
+}
+
+#[async_trait]
+impl Actor for Connection {
+    type Args = ();
+    type Msg = ConnMsg;
+
+    async fn start(this: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        this.send_after(Duration::from_millis(50), ConnMsg::Connected);
+        Ok(Self {
+            connected: false,
+            sent: 0,
+        })
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            ConnMsg::Connected => {
+                println!("connected, replaying {} stashed messages", this.stash_len());
+                self.connected = true;
+                this.unstash_all();
+            }
+            ConnMsg::Send(line, reply) if self.connected => {
+                self.sent += 1;
+                println!("sent {line:?}");
+                reply.send(self.sent)?;
+            }
+            msg @ ConnMsg::Send(..) => {
+                if let Err(ActorError::StashOverflow(capacity)) = this.stash(msg) {
+                    println!("stash full at {capacity} messages, dropping");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let spec = ActorSpec::new().stash(3);
+    let conn = scope.spawn_with::<Connection>(spec, ()).await;
+
+    let mut replies = vec![];
+    for i in 0..4 {
+        let conn = conn.clone();
+        replies.push(tokio::spawn(async move {
+            conn.call(|reply| ConnMsg::Send(format!("query {i}"), reply))
+                .await
+        }));
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+
+    for reply in replies {
+        match reply.await.unwrap() {
+            Ok(sent) => println!("reply: {sent}"),
+            Err(e) => println!("reply failed: {e:?}"),
+        }
+    }
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
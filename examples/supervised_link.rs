@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, LinkPublisher, Reply, RestartStrategy,
+    Scope, ScopeContext, SupervisorSpec, WorkerSpec,
+};
+
+enum CounterMsg {
+    Increment(Reply<u32>),
+    Crash,
+}
+
+struct Counter {
+    count: u32,
+}
+
+#[async_trait]
+impl Actor for Counter {
+    type Args = ();
+    type Msg = CounterMsg;
+
+    async fn start(this: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        println!("Counter {:?} started", this.id());
+        Ok(Self { count: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            CounterMsg::Increment(reply) => {
+                self.count += 1;
+                reply.send(self.count)?;
+                Ok(())
+            }
+            CounterMsg::Crash => Err(ActorError::HandleClosed),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let (publisher, counter) = LinkPublisher::new();
+
+    let tree = SupervisorSpec::new(RestartStrategy::OneForOne)
+        .child(WorkerSpec::<Counter>::new("counter", || ()).publish(publisher));
+
+    let root = scope.start_tree(tree).await;
+
+    for _ in 0..3 {
+        let count = counter.call(CounterMsg::Increment).await?;
+        println!("Count: {count}");
+    }
+
+    counter.cast(CounterMsg::Crash).await?;
+
+    // The link follows the restarted counter, starting again from zero
+    loop {
+        match counter.call(CounterMsg::Increment).await {
+            Ok(count) => {
+                println!("Count after restart: {count}");
+                break;
+            }
+            Err(e) => {
+                println!("Counter unavailable: {}", ActorError::from(e));
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }
+    }
+
+    scope.exit_actor_and_wait(root.id()).await;
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorResult, ActorSelf, ExitReason, Restart, RestartStrategy, Scope, ScopeContext,
+    SupervisorMsg, SupervisorSpec, WorkerSpec, call,
+};
+
+struct Service {
+    name: &'static str,
+    exit_delay: Duration,
+}
+
+#[async_trait]
+impl Actor for Service {
+    type Args = (&'static str, Duration);
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, (name, exit_delay): Self::Args) -> ActorResult<Self> {
+        println!("{name} started");
+        Ok(Self { name, exit_delay })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        tokio::time::sleep(self.exit_delay).await;
+        println!("{} exited", self.name);
+        Ok(())
+    }
+}
+
+fn service(name: &'static str) -> WorkerSpec<Service> {
+    WorkerSpec::new(name, move || (name, Duration::ZERO))
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let http = SupervisorSpec::new(RestartStrategy::OneForAll)
+        .child(service("router"))
+        .child(service("listener").mailbox(1000));
+
+    let tree = SupervisorSpec::new(RestartStrategy::RestForOne)
+        .intensity(5, Duration::from_secs(10))
+        .child(service("database"))
+        .child(service("cache").restart(Restart::Transient))
+        .supervisor("http", http)
+        .child(
+            WorkerSpec::<Service>::new("metrics", || ("metrics", Duration::from_secs(60)))
+                .restart(Restart::Temporary)
+                .shutdown(Some(Duration::from_millis(100))),
+        );
+
+    let root = scope.start_tree(tree).await;
+
+    let children = call!(root, SupervisorMsg::WhichChildren).await?;
+    println!("Children: {:?}", children);
+
+    scope.exit_actor_and_wait(root.id()).await;
+
+    while let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {:?}, {:?}", id, output);
+    }
+
+    Ok(())
+}
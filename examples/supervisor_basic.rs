@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, RestartStrategy, Scope, ScopeContext,
+    Supervisor, SupervisorMsg, SupervisorSpec, WorkerSpec, call,
+};
+
+struct Flaky {
+    name: &'static str,
+}
+
+#[async_trait]
+impl Actor for Flaky {
+    // (name, incarnation, incarnations that fail to start)
+    type Args = (&'static str, u32, u32);
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, args: Self::Args) -> ActorResult<Self> {
+        let (name, incarnation, failures) = args;
+        if incarnation <= failures {
+            println!("{name} #{incarnation} failed to start");
+            return Err(ActorError::HandleClosed);
+        }
+        println!("{name} #{incarnation} started");
+        Ok(Self { name })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("{} exiting", self.name);
+        Ok(())
+    }
+}
+
+fn flaky(name: &'static str, failures: u32) -> WorkerSpec<Flaky> {
+    let mut incarnation = 0;
+    WorkerSpec::new(name, move || {
+        incarnation += 1;
+        (name, incarnation, failures)
+    })
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let spec = SupervisorSpec::new(RestartStrategy::OneForOne)
+        .intensity(3, Duration::from_secs(1))
+        .child(flaky("db", 0))
+        .child(flaky("cache", 2));
+
+    let sup = scope.spawn::<Supervisor>(spec).await;
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let children = call!(sup, SupervisorMsg::WhichChildren).await?;
+    println!("Children: {:?}", children);
+
+    let spec = SupervisorSpec::new(RestartStrategy::OneForAll)
+        .intensity(3, Duration::from_secs(1))
+        .child(flaky("web", 0))
+        .child(flaky("broken", u32::MAX));
+
+    let _sup = scope.spawn::<Supervisor>(spec).await;
+
+    if let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {:?}, {:?}", id, output);
+    }
+
+    scope.exit_and_wait().await;
+
+    Ok(())
+}
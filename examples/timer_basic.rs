@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext, TimerRef};
+
+#[derive(Debug)]
+enum Msg {
+    Tick(u32),
+    Cancelled,
+    Stop,
+}
+
+struct Ticker {
+    ticks: TimerRef,
+}
+
+#[async_trait]
+impl Actor for Ticker {
+    type Args = ();
+    type Msg = Msg;
+
+    async fn start(this: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        let mut n = 0;
+        let ticks = this.send_interval(Duration::from_millis(50), move || {
+            n += 1;
+            Msg::Tick(n)
+        });
+
+        let cancelled = this.send_after(Duration::from_millis(100), Msg::Cancelled);
+        cancelled.cancel();
+
+        this.send_after(Duration::from_millis(275), Msg::Stop);
+
+        Ok(Self { ticks })
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Tick(n) => println!("Tick {n}"),
+            Msg::Cancelled => println!("Cancelled timer fired"),
+            Msg::Stop => {
+                self.ticks.cancel();
+                this.exit();
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        println!("Ticker exiting");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let _ticker = scope.spawn::<Ticker>(()).await;
+
+    while let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {:?}, {:?}", id, output);
+    }
+}
@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use xacto::{Actor, ActorResult, ActorSelf, ExitReason, Reply, Scope, ScopeContext, call};
+
+enum SessionMsg {
+    Touch(Reply<usize>),
+}
+
+struct Session {
+    touches: usize,
+}
+
+#[async_trait]
+impl Actor for Session {
+    type Args = ();
+    type Msg = SessionMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { touches: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            SessionMsg::Touch(reply) => {
+                self.touches += 1;
+                reply.send(self.touches)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, reason: &ExitReason) -> ActorResult {
+        println!("Session exiting: {reason}");
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() -> ActorResult {
+    let context = ScopeContext::new();
+    let mut scope = Scope::new(context);
+
+    let session = scope.spawn::<Session>(()).await;
+
+    // e.g. a cache of sessions that shouldn't keep them alive
+    let cached = session.downgrade();
+
+    if let Some(session) = cached.upgrade() {
+        println!("touches: {}", call!(session, SessionMsg::Touch).await?);
+    }
+
+    // The last strong handle is dropped, so the session's mailbox closes and it exits
+    drop(session);
+
+    if let Some((id, output)) = scope.next_finished().await {
+        println!("Finished: {id:?}, {output:?}");
+    }
+    println!("upgrade after exit: {:?}", cached.upgrade());
+
+    Ok(())
+}
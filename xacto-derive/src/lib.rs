@@ -205,7 +205,7 @@ fn parse_rpc_message(input: DeriveInput) -> Result<TokenStream, Error> {
                     let msg = #original_arm;
                     let (msg, act) = f(msg).ok_or(())?;
                     act.cast(msg).await.map_err(|_| ())?;
-                    let response = rx.await.unwrap();
+                    let response = rx.await.map_err(|_| ())?;
                     let env = ::xacto::RpcEnvelope {
                         id: env.id,
                         payload: #response_arm,
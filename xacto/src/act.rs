@@ -1,6 +1,14 @@
-use tokio::sync::{mpsc, oneshot};
+use std::{sync::Weak, time::Duration};
 
-use crate::{ActorId, CallError, RecvError, SendError};
+use tokio::{
+    sync::{mpsc, oneshot},
+    time::Instant,
+};
+
+use crate::{
+    ActorId, CallError, Down, ExitSignal, Overflow, Recipient, RecvError, SendError, TimerRef,
+    mailbox::{MailboxTx, WeakMailboxRx, WeakMailboxTx},
+};
 
 #[derive(Debug)]
 pub struct Reply<T> {
@@ -19,20 +27,37 @@ impl<T: Send + 'static> Reply<T> {
 
 pub enum ActorSignal<M> {
     Msg(M),
+    // Dropped unhandled if dequeued after the deadline
+    Call(M, Instant),
+    Down(Down),
+    Exit(ExitSignal),
+    Timer(TimerRef, M),
 }
 
 impl<M> ActorSignal<M> {
     fn unwrap_msg(self) -> M {
         match self {
             ActorSignal::Msg(msg) => msg,
+            ActorSignal::Call(msg, _) => msg,
             _ => panic!("expected msg"),
         }
     }
+
+    // The message of a `Msg` or a `Call` whose deadline hasn't passed
+    pub(crate) fn into_live_msg(self) -> Option<M> {
+        match self {
+            ActorSignal::Msg(msg) => Some(msg),
+            ActorSignal::Call(msg, deadline) if Instant::now() < deadline => Some(msg),
+            _ => None,
+        }
+    }
 }
 
 pub struct Act<Msg> {
     id: ActorId,
-    tx: mpsc::Sender<ActorSignal<Msg>>,
+    tx: MailboxTx<Msg>,
+    rx: WeakMailboxRx<Msg>,
+    overflow: Overflow,
 }
 
 impl<Msg> std::fmt::Debug for Act<Msg> {
@@ -42,42 +67,202 @@ impl<Msg> std::fmt::Debug for Act<Msg> {
 }
 
 impl<Msg> Act<Msg> {
+    // `Overflow::DropOldest` behaves like `Overflow::Fail` for handles created this way, since
+    // they have no access to the receiving end to evict from
     pub fn new(id: ActorId, tx: mpsc::Sender<ActorSignal<Msg>>) -> Self {
-        Self { id, tx }
+        Self::from_mailbox(id, MailboxTx::Bounded(tx), Weak::new())
+    }
+
+    pub(crate) fn from_mailbox(id: ActorId, tx: MailboxTx<Msg>, rx: WeakMailboxRx<Msg>) -> Self {
+        Self {
+            id,
+            tx,
+            rx,
+            overflow: Overflow::Block,
+        }
+    }
+
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
     }
 
     pub fn id(&self) -> ActorId {
         self.id
     }
 
-    pub fn tx(&self) -> mpsc::Sender<ActorSignal<Msg>> {
-        self.tx.clone()
+    // `None` for an unbounded mailbox
+    pub fn tx(&self) -> Option<mpsc::Sender<ActorSignal<Msg>>> {
+        match &self.tx {
+            MailboxTx::Bounded(tx) => Some(tx.clone()),
+            MailboxTx::Unbounded(_) => None,
+        }
+    }
+
+    // Free space in the mailbox, `usize::MAX` for an unbounded one
+    pub fn capacity(&self) -> usize {
+        match &self.tx {
+            MailboxTx::Bounded(tx) => tx.capacity(),
+            MailboxTx::Unbounded(_) => usize::MAX,
+        }
+    }
+
+    pub fn downgrade(&self) -> WeakAct<Msg> {
+        WeakAct {
+            id: self.id,
+            tx: self.tx.downgrade(),
+            rx: self.rx.clone(),
+            overflow: self.overflow,
+        }
     }
 
     fn create_signal(&self, msg: Msg) -> ActorSignal<Msg> {
         ActorSignal::Msg(msg)
     }
 
+    // Makes room in a full `Overflow::DropOldest` mailbox. Returns false if the send should fail
+    // instead, including when the mailbox is full only of reserved permits and there is nothing
+    // to evict.
+    fn evict_oldest(&self) -> bool {
+        if self.overflow != Overflow::DropOldest {
+            return false;
+        }
+        let Some(rx) = self.rx.upgrade() else {
+            return false;
+        };
+        let Ok(mut rx) = rx.lock() else {
+            return false;
+        };
+        rx.try_recv().is_some()
+    }
+
+    async fn send_signal(&self, signal: ActorSignal<Msg>) -> Result<(), SendError<Msg>> {
+        match (&self.tx, self.overflow) {
+            (MailboxTx::Bounded(tx), Overflow::Block) => tx
+                .send(signal)
+                .await
+                .map_err(|e| SendError::Closed(e.0.unwrap_msg())),
+            _ => self.try_send_signal(signal),
+        }
+    }
+
+    fn try_send_signal(&self, mut signal: ActorSignal<Msg>) -> Result<(), SendError<Msg>> {
+        let tx = match &self.tx {
+            MailboxTx::Bounded(tx) => tx,
+            MailboxTx::Unbounded(tx) => {
+                return tx
+                    .send(signal)
+                    .map_err(|e| SendError::Closed(e.0.unwrap_msg()));
+            }
+        };
+        loop {
+            match tx.try_send(signal) {
+                Ok(()) => return Ok(()),
+                Err(mpsc::error::TrySendError::Full(_))
+                    if self.overflow == Overflow::DropNewest =>
+                {
+                    return Ok(());
+                }
+                Err(mpsc::error::TrySendError::Full(e)) if self.evict_oldest() => signal = e,
+                Err(mpsc::error::TrySendError::Full(e)) => {
+                    return Err(SendError::Full(e.unwrap_msg()));
+                }
+                Err(mpsc::error::TrySendError::Closed(e)) => {
+                    return Err(SendError::Closed(e.unwrap_msg()));
+                }
+            }
+        }
+    }
+
+    // Maps `value` into a signal only once there is room for it, so that it can be handed back
+    // if the mailbox is full or closed
+    pub(crate) async fn send_mapped<N, F>(&self, value: N, f: F) -> Result<(), SendError<N>>
+    where
+        F: FnOnce(N) -> ActorSignal<Msg>,
+    {
+        match (&self.tx, self.overflow) {
+            (MailboxTx::Bounded(tx), Overflow::Block) => match tx.reserve().await {
+                Ok(permit) => {
+                    permit.send(f(value));
+                    Ok(())
+                }
+                Err(_) => Err(SendError::Closed(value)),
+            },
+            _ => self.try_send_mapped(value, f),
+        }
+    }
+
+    pub(crate) fn try_send_mapped<N, F>(&self, value: N, f: F) -> Result<(), SendError<N>>
+    where
+        F: FnOnce(N) -> ActorSignal<Msg>,
+    {
+        let tx = match &self.tx {
+            MailboxTx::Bounded(tx) => tx,
+            MailboxTx::Unbounded(tx) => {
+                if tx.is_closed() {
+                    return Err(SendError::Closed(value));
+                }
+                // Lost if the actor stops in between, as with any message it doesn't get to
+                let _ = tx.send(f(value));
+                return Ok(());
+            }
+        };
+        loop {
+            match tx.try_reserve() {
+                Ok(permit) => {
+                    permit.send(f(value));
+                    return Ok(());
+                }
+                Err(mpsc::error::TrySendError::Full(()))
+                    if self.overflow == Overflow::DropNewest =>
+                {
+                    return Ok(());
+                }
+                Err(mpsc::error::TrySendError::Full(())) if self.evict_oldest() => {}
+                Err(mpsc::error::TrySendError::Full(())) => return Err(SendError::Full(value)),
+                Err(mpsc::error::TrySendError::Closed(())) => {
+                    return Err(SendError::Closed(value));
+                }
+            }
+        }
+    }
+
+    pub fn contramap<N, F>(&self, f: F) -> Recipient<N>
+    where
+        Msg: Send + 'static,
+        N: Send + 'static,
+        F: Fn(N) -> Msg + Send + Sync + 'static,
+    {
+        Recipient::new(self.clone(), f)
+    }
+
+    pub fn recipient<N>(&self) -> Recipient<N>
+    where
+        Msg: From<N> + Send + 'static,
+        N: Send + 'static,
+    {
+        self.contramap(Msg::from)
+    }
+
     pub async fn cast(&self, msg: Msg) -> Result<(), SendError<Msg>> {
         let signal = self.create_signal(msg);
-        if let Err(e) = self.tx.send(signal).await {
-            return Err(SendError::Closed(e.0.unwrap_msg()));
-        }
-        Ok(())
+        self.send_signal(signal).await
     }
 
     pub fn try_cast(&self, msg: Msg) -> Result<(), SendError<Msg>> {
         let signal = self.create_signal(msg);
-        if let Err(e) = self.tx.try_send(signal) {
-            return match e {
-                mpsc::error::TrySendError::Full(e) => Err(SendError::Full(e.unwrap_msg())),
-                mpsc::error::TrySendError::Closed(e) => Err(SendError::Closed(e.unwrap_msg())),
-            };
-        }
-        Ok(())
+        self.try_send_signal(signal)
     }
 
     pub async fn call_manually<T, F>(&self, f: F) -> Result<T, CallError<Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        self.call(f).await
+    }
+
+    pub async fn call<T, F>(&self, f: F) -> Result<T, CallError<Msg>>
     where
         T: Send + 'static,
         F: FnOnce(Reply<T>) -> Msg,
@@ -86,33 +271,37 @@ impl<Msg> Act<Msg> {
         let msg = f(Reply::new(tx));
         let signal = self.create_signal(msg);
 
-        if let Err(e) = self.tx.send(signal).await {
-            return Err(CallError::Send(SendError::Closed(e.0.unwrap_msg())));
-        }
+        self.send_signal(signal).await.map_err(CallError::Send)?;
 
-        match rx.await {
-            Ok(result) => Ok(result),
-            Err(_) => Err(CallError::Recv(RecvError::Closed)),
-        }
+        recv_reply(rx, None).await.map_err(CallError::Recv)
     }
 
-    pub async fn call<T, F>(&self, f: F) -> Result<T, CallError<Msg>>
+    pub async fn call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        self.call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn call_deadline<T, F>(&self, deadline: Instant, f: F) -> Result<T, CallError<Msg>>
     where
         T: Send + 'static,
         F: FnOnce(Reply<T>) -> Msg,
     {
         let (tx, rx) = oneshot::channel();
         let msg = f(Reply::new(tx));
-        let signal = self.create_signal(msg);
+        let signal = ActorSignal::Call(msg, deadline);
 
-        if let Err(e) = self.tx.send(signal).await {
-            return Err(CallError::Send(SendError::Closed(e.0.unwrap_msg())));
+        match tokio::time::timeout_at(deadline, self.send_signal(signal)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(CallError::Send(e)),
+            Err(_) => return Err(CallError::Recv(RecvError::Timeout)),
         }
 
-        match rx.await {
-            Ok(result) => Ok(result),
-            Err(_) => Err(CallError::Recv(RecvError::Closed)),
-        }
+        recv_reply(rx, Some(deadline))
+            .await
+            .map_err(CallError::Recv)
     }
 
     pub async fn try_call<T, F>(&self, f: F) -> Result<T, CallError<Msg>>
@@ -124,22 +313,52 @@ impl<Msg> Act<Msg> {
         let msg = f(Reply::new(tx));
         let signal = self.create_signal(msg);
 
-        if let Err(e) = self.tx.try_send(signal) {
-            return match e {
-                mpsc::error::TrySendError::Full(e) => {
-                    Err(CallError::Send(SendError::Full(e.unwrap_msg())))
-                }
-                mpsc::error::TrySendError::Closed(e) => {
-                    Err(CallError::Send(SendError::Closed(e.unwrap_msg())))
-                }
-            };
-        }
+        self.try_send_signal(signal).map_err(CallError::Send)?;
 
-        match rx.await {
-            Ok(result) => Ok(result),
-            Err(_) => Err(CallError::Recv(RecvError::Closed)),
-        }
+        recv_reply(rx, None).await.map_err(CallError::Recv)
     }
+
+    pub async fn try_call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        self.try_call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn try_call_deadline<T, F>(
+        &self,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, CallError<Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+        let signal = ActorSignal::Call(msg, deadline);
+
+        self.try_send_signal(signal).map_err(CallError::Send)?;
+
+        recv_reply(rx, Some(deadline))
+            .await
+            .map_err(CallError::Recv)
+    }
+}
+
+pub(crate) async fn recv_reply<T>(
+    rx: oneshot::Receiver<T>,
+    deadline: Option<Instant>,
+) -> Result<T, RecvError> {
+    let result = match deadline {
+        Some(deadline) => match tokio::time::timeout_at(deadline, rx).await {
+            Ok(result) => result,
+            Err(_) => return Err(RecvError::Timeout),
+        },
+        None => rx.await,
+    };
+    result.map_err(|_| RecvError::Closed)
 }
 
 impl<Msg> Clone for Act<Msg> {
@@ -147,6 +366,45 @@ impl<Msg> Clone for Act<Msg> {
         Self {
             id: self.id,
             tx: self.tx.clone(),
+            rx: self.rx.clone(),
+            overflow: self.overflow,
         }
     }
 }
+
+// A handle that doesn't keep the actor's mailbox open. Once every `Act` to an actor has been
+// dropped, the actor exits with `ExitReason::Normal` after handling what is left in its mailbox.
+pub struct WeakAct<Msg> {
+    id: ActorId,
+    tx: WeakMailboxTx<Msg>,
+    rx: WeakMailboxRx<Msg>,
+    overflow: Overflow,
+}
+
+impl<Msg> WeakAct<Msg> {
+    pub fn id(&self) -> ActorId {
+        self.id
+    }
+
+    pub fn upgrade(&self) -> Option<Act<Msg>> {
+        let tx = self.tx.upgrade()?;
+        Some(Act::from_mailbox(self.id, tx, self.rx.clone()).with_overflow(self.overflow))
+    }
+}
+
+impl<Msg> Clone for WeakAct<Msg> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            tx: self.tx.clone(),
+            rx: self.rx.clone(),
+            overflow: self.overflow,
+        }
+    }
+}
+
+impl<Msg> std::fmt::Debug for WeakAct<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WeakAct({:?})", self.id)
+    }
+}
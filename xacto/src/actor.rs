@@ -1,12 +1,23 @@
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
-use crate::{ActorError, ActorSelf};
+use crate::{ActorError, ActorSelf, Down, ExitReason, ExitSignal, TimerRef};
 
 pub type ActorResult<T = ()> = Result<T, ActorError>;
 
+// What the actor task does after a handler returned an error
+#[derive(Debug)]
+pub enum ErrorAction {
+    // Drop the failed message and keep going
+    Resume,
+    // Replace the actor with a new incarnation started from `Actor::pre_restart`
+    Restart(ActorError),
+    // Fail the actor with the error
+    Stop(ActorError),
+}
+
 // (scope_id, actor_task_id)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct ActorId(pub u32, pub u32);
 
 #[async_trait]
@@ -22,5 +33,70 @@ pub trait Actor: Send + 'static {
     where
         Self: Sized;
 
-    async fn exit(&mut self) -> ActorResult;
+    async fn receive_batch(&mut self, this: &ActorSelf<Self>, msgs: Vec<Self::Msg>) -> ActorResult
+    where
+        Self: Sized,
+    {
+        for msg in msgs {
+            self.receive(this, msg).await?;
+        }
+        Ok(())
+    }
+
+    async fn handle_timer(
+        &mut self,
+        this: &ActorSelf<Self>,
+        _timer: TimerRef,
+        msg: Self::Msg,
+    ) -> ActorResult
+    where
+        Self: Sized,
+    {
+        self.receive(this, msg).await
+    }
+
+    async fn handle_down(&mut self, _this: &ActorSelf<Self>, _down: Down) -> ActorResult
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+
+    async fn handle_exit(&mut self, _this: &ActorSelf<Self>, _exit: ExitSignal) -> ActorResult
+    where
+        Self: Sized,
+    {
+        Ok(())
+    }
+
+    // Called when `receive` or any of the signal handlers returns an error
+    async fn on_receive_error(&mut self, _this: &ActorSelf<Self>, error: ActorError) -> ErrorAction
+    where
+        Self: Sized,
+    {
+        ErrorAction::Stop(error)
+    }
+
+    // Returns the args for the next incarnation on `ErrorAction::Restart`. The stash, timers,
+    // monitors and links are kept across the restart. An error stops the actor with it.
+    async fn pre_restart(
+        &mut self,
+        _this: &ActorSelf<Self>,
+        error: ActorError,
+    ) -> ActorResult<Self::Args>
+    where
+        Self: Sized,
+    {
+        Err(error)
+    }
+
+    // Only called when the actor is stopped through `exit_with` or its scope, not when it fails
+    async fn exit(&mut self, reason: &ExitReason) -> ActorResult;
+
+    // Called once the actor has stopped, however it stopped short of panicking or being aborted
+    async fn post_stop(&mut self, _this: &ActorSelf<Self>)
+    where
+        Self: Sized,
+    {
+    }
 }
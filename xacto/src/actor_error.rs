@@ -1,3 +1,5 @@
+use crate::ActorId;
+
 #[derive(Debug)]
 pub enum CallError<M> {
     Send(SendError<M>),
@@ -7,12 +9,26 @@ pub enum CallError<M> {
 #[derive(Debug)]
 pub enum ActorError {
     HandleClosed,
+    NoProc(ActorId),
+    MaxRestarts,
+    StashOverflow(usize),
+    Registry(RegistryError),
     Link(LinkError<(), ()>),
     Send(SendError<()>),
     Recv(RecvError),
     Unknown(Box<dyn std::error::Error + Send + 'static>),
 }
 
+#[derive(Debug)]
+pub enum RegistryError {
+    // The name is taken by another actor
+    AlreadyRegistered(String, ActorId),
+    // The name is registered with a different message type
+    WrongType(String),
+    NoProc(ActorId),
+    EmptyGroup(String),
+}
+
 #[derive(Debug)]
 pub enum LinkError<U, M> {
     Unavailable(U),
@@ -26,7 +42,7 @@ pub enum SendError<M> {
     Closed(M),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RecvError {
     Timeout,
     Closed,
@@ -36,6 +52,12 @@ impl std::fmt::Display for ActorError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ActorError::HandleClosed => write!(f, "actor handle closed"),
+            ActorError::NoProc(id) => write!(f, "actor {id:?} is not running"),
+            ActorError::MaxRestarts => write!(f, "supervisor restart intensity exceeded"),
+            ActorError::StashOverflow(capacity) => {
+                write!(f, "actor stash overflowed its capacity of {capacity}")
+            }
+            ActorError::Registry(e) => write!(f, "actor registry error: {e}"),
             ActorError::Link(e) => write!(f, "actor link error: {e:?}"),
             ActorError::Send(e) => write!(f, "actor send error: {e:?}"),
             ActorError::Recv(e) => write!(f, "actor recv error: {e:?}"),
@@ -44,6 +66,24 @@ impl std::fmt::Display for ActorError {
     }
 }
 
+impl std::fmt::Display for RegistryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::AlreadyRegistered(name, id) => {
+                write!(f, "name {name:?} is already registered to {id:?}")
+            }
+            RegistryError::WrongType(name) => {
+                write!(
+                    f,
+                    "name {name:?} is registered with a different message type"
+                )
+            }
+            RegistryError::NoProc(id) => write!(f, "actor {id:?} is not running"),
+            RegistryError::EmptyGroup(name) => write!(f, "group {name:?} has no members"),
+        }
+    }
+}
+
 impl<E> From<E> for ActorError
 where
     E: std::error::Error + Send + 'static,
@@ -70,3 +110,19 @@ impl<M> From<CallError<M>> for ActorError {
         }
     }
 }
+
+impl<U, M> From<LinkError<U, M>> for ActorError {
+    fn from(value: LinkError<U, M>) -> Self {
+        match value {
+            LinkError::Unavailable(_) => ActorError::Link(LinkError::Unavailable(())),
+            LinkError::Send(e) => e.into(),
+            LinkError::Call(e) => e.into(),
+        }
+    }
+}
+
+impl From<RegistryError> for ActorError {
+    fn from(value: RegistryError) -> Self {
+        ActorError::Registry(value)
+    }
+}
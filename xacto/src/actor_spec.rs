@@ -0,0 +1,74 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mailbox {
+    Bounded(usize),
+    Unbounded,
+}
+
+// What a send does when the mailbox is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    // Wait for space in the mailbox; `try_cast` and `try_call` still fail with `SendError::Full`
+    #[default]
+    Block,
+    // Fail with `SendError::Full`
+    Fail,
+    // Discard the message being sent
+    DropNewest,
+    // Discard the oldest queued message to make room
+    DropOldest,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActorSpec {
+    pub(crate) mailbox: Mailbox,
+    pub(crate) overflow: Overflow,
+    pub(crate) batch: Option<usize>,
+    pub(crate) stash: Option<usize>,
+}
+
+impl ActorSpec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn bounded(mut self, capacity: usize) -> Self {
+        assert!(capacity > 0, "mailbox capacity must be greater than 0");
+        self.mailbox = Mailbox::Bounded(capacity);
+        self
+    }
+
+    pub fn unbounded(mut self) -> Self {
+        self.mailbox = Mailbox::Unbounded;
+        self
+    }
+
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    // Deliver up to `max` queued messages at a time through `Actor::receive_batch`
+    pub fn batch(mut self, max: usize) -> Self {
+        assert!(max > 0, "batch size must be greater than 0");
+        self.batch = Some(max);
+        self
+    }
+
+    // Limits how many messages `ActorSelf::stash` holds before failing with
+    // `ActorError::StashOverflow`
+    pub fn stash(mut self, capacity: usize) -> Self {
+        self.stash = Some(capacity);
+        self
+    }
+}
+
+impl Default for ActorSpec {
+    fn default() -> Self {
+        Self {
+            mailbox: Mailbox::Bounded(100),
+            overflow: Overflow::Block,
+            batch: None,
+            stash: None,
+        }
+    }
+}
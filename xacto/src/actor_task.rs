@@ -1,36 +1,219 @@
-use tokio::sync::mpsc;
+use std::{
+    collections::VecDeque,
+    future::poll_fn,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::{sync::mpsc, time::Instant};
 use tokio_util::sync::CancellationToken;
 
-use crate::{Act, Actor, ActorError, ActorId, ActorSignal};
+use crate::{
+    Act, Actor, ActorError, ActorId, ActorResult, ActorSignal, ActorSpec, ErrorAction, ExitReason,
+    ExitSignal, GroupMembers, MonitorRef, RegistryError, ScopeContext, TimerRef, WeakAct,
+    mailbox::SharedMailboxRx, timer,
+};
 
 pub type ActorTaskResult = Result<(), ActorTaskError>;
 
 pub struct ActorSelf<A: Actor> {
-    act: Act<A::Msg>,
-    rx: mpsc::Receiver<ActorSignal<A::Msg>>,
+    // Weak, so that the actor exits once every other handle to it has been dropped
+    act: WeakAct<A::Msg>,
+    rx: SharedMailboxRx<A::Msg>,
+    signals_tx: mpsc::UnboundedSender<ActorSignal<A::Msg>>,
+    signals: mpsc::UnboundedReceiver<ActorSignal<A::Msg>>,
     cancel: CancellationToken,
+    context: Arc<Mutex<ScopeContext>>,
+    trap_exit: AtomicBool,
+    next_timer_id: AtomicU64,
+    stash: Mutex<VecDeque<A::Msg>>,
+    unstashed: Mutex<VecDeque<A::Msg>>,
+    exit_reason: Mutex<Option<ExitReason>>,
+    spec: ActorSpec,
 }
 
 impl<A: Actor> ActorSelf<A> {
-    pub fn new(
+    pub(crate) fn new(
         act: Act<A::Msg>,
-        rx: mpsc::Receiver<ActorSignal<A::Msg>>,
+        rx: SharedMailboxRx<A::Msg>,
+        signals_tx: mpsc::UnboundedSender<ActorSignal<A::Msg>>,
+        signals: mpsc::UnboundedReceiver<ActorSignal<A::Msg>>,
         cancel: CancellationToken,
+        context: Arc<Mutex<ScopeContext>>,
     ) -> Self {
-        Self { act, rx, cancel }
+        Self {
+            act: act.downgrade(),
+            rx,
+            signals_tx,
+            signals,
+            cancel,
+            context,
+            trap_exit: AtomicBool::new(false),
+            next_timer_id: AtomicU64::new(0),
+            stash: Mutex::new(VecDeque::new()),
+            unstashed: Mutex::new(VecDeque::new()),
+            exit_reason: Mutex::new(None),
+            spec: ActorSpec::default(),
+        }
+    }
+
+    pub(crate) fn with_spec(mut self, spec: ActorSpec) -> Self {
+        self.spec = spec;
+        self
     }
 
     pub fn id(&self) -> ActorId {
         self.act.id()
     }
 
-    pub fn act(&self) -> &Act<A::Msg> {
+    // `None` once every `Act` to this actor has been dropped and it is handling the last of its
+    // mailbox
+    pub fn act(&self) -> Option<Act<A::Msg>> {
+        self.act.upgrade()
+    }
+
+    pub fn weak_act(&self) -> &WeakAct<A::Msg> {
         &self.act
     }
 
+    pub fn context(&self) -> &Arc<Mutex<ScopeContext>> {
+        &self.context
+    }
+
+    pub fn monitor(&self, target: ActorId) -> MonitorRef {
+        self.context.lock().unwrap().monitor(self.id(), target)
+    }
+
+    pub fn demonitor(&self, monitor: MonitorRef) {
+        self.context.lock().unwrap().demonitor(monitor);
+    }
+
+    pub fn link(&self, id: ActorId) -> ActorResult {
+        self.context.lock().unwrap().link(self.id(), id)
+    }
+
+    pub fn unlink(&self, id: ActorId) {
+        self.context.lock().unwrap().unlink(self.id(), id);
+    }
+
+    pub fn register(&self, name: impl Into<String>) -> Result<(), RegistryError> {
+        let act = self.act().ok_or(RegistryError::NoProc(self.id()))?;
+        self.context.lock().unwrap().register(name, act)
+    }
+
+    pub fn whereis<Msg: Send + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<Option<Act<Msg>>, RegistryError> {
+        self.context.lock().unwrap().whereis(name)
+    }
+
+    pub fn join(&self, group: impl Into<String>) -> Result<(), RegistryError> {
+        let act = self.act().ok_or(RegistryError::NoProc(self.id()))?;
+        self.context.lock().unwrap().join(group, act)
+    }
+
+    pub fn leave(&self, group: &str) {
+        self.context.lock().unwrap().leave(group, self.id());
+    }
+
+    pub fn members<Msg: Send + 'static>(
+        &self,
+        group: &str,
+    ) -> Result<GroupMembers<Msg>, RegistryError> {
+        self.context.lock().unwrap().members(group)
+    }
+
+    // When set, exit signals from linked actors are passed to `Actor::handle_exit` instead of
+    // stopping this actor
+    pub fn trap_exit(&self, trap_exit: bool) {
+        self.trap_exit.store(trap_exit, Ordering::Relaxed);
+    }
+
+    pub fn is_trapping_exit(&self) -> bool {
+        self.trap_exit.load(Ordering::Relaxed)
+    }
+
+    // Timers are cancelled automatically when the actor exits
+    pub fn send_after(&self, delay: Duration, msg: A::Msg) -> TimerRef {
+        let id = self.next_timer_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = self.cancel.child_token();
+        timer::send_after(id, cancel, self.signals_tx.clone(), delay, msg)
+    }
+
+    pub fn send_interval<F>(&self, period: Duration, f: F) -> TimerRef
+    where
+        F: FnMut() -> A::Msg + Send + 'static,
+    {
+        assert!(!period.is_zero(), "timer period must be greater than 0");
+        let id = self.next_timer_id.fetch_add(1, Ordering::Relaxed);
+        let cancel = self.cancel.child_token();
+        timer::send_interval(id, cancel, self.signals_tx.clone(), period, f)
+    }
+
+    // Takes every message currently queued in the mailbox, for handlers that want to coalesce
+    // pending work
+    pub fn drain(&self) -> Vec<A::Msg> {
+        let mut rx = self.rx.lock().unwrap();
+        let mut msgs = vec![];
+        while let Some(signal) = rx.try_recv() {
+            msgs.extend(signal.into_live_msg());
+        }
+        msgs
+    }
+
+    // Sets `msg` aside until `unstash_all`, for messages the actor can't handle in its current
+    // state
+    pub fn stash(&self, msg: A::Msg) -> ActorResult {
+        let mut stash = self.stash.lock().unwrap();
+        if let Some(capacity) = self.spec.stash
+            && stash.len() >= capacity
+        {
+            return Err(ActorError::StashOverflow(capacity));
+        }
+        stash.push_back(msg);
+        Ok(())
+    }
+
+    // Stashed messages are handled in the order they were stashed, before any new messages
+    pub fn unstash_all(&self) {
+        let mut stash = std::mem::take(&mut *self.stash.lock().unwrap());
+        let mut unstashed = self.unstashed.lock().unwrap();
+        stash.append(&mut unstashed);
+        *unstashed = stash;
+    }
+
+    pub fn stash_len(&self) -> usize {
+        self.stash.lock().unwrap().len()
+    }
+
+    fn take_unstashed(&self, limit: usize) -> Vec<A::Msg> {
+        let mut unstashed = self.unstashed.lock().unwrap();
+        let count = limit.min(unstashed.len());
+        unstashed.drain(..count).collect()
+    }
+
     pub fn exit(&self) {
+        self.exit_with(ExitReason::Normal);
+    }
+
+    // Only the first reason is kept. An actor stopped by its scope without a reason exits with
+    // `ExitReason::Shutdown`
+    pub fn exit_with(&self, reason: ExitReason) {
+        self.exit_reason.lock().unwrap().get_or_insert(reason);
         self.cancel.cancel();
     }
+
+    fn take_exit_reason(&self) -> ExitReason {
+        self.exit_reason
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap_or(ExitReason::Shutdown)
+    }
 }
 
 #[derive(Debug)]
@@ -38,34 +221,110 @@ pub enum ActorTaskError {
     Start(ActorError),
     Receive(ActorError),
     Exit(ActorError),
+    Linked(ExitSignal),
 }
 
 pub struct ActorTask<A: Actor> {
     this: ActorSelf<A>,
+    started: CancellationToken,
 }
 
 impl<A: Actor> ActorTask<A> {
     pub fn new(this: ActorSelf<A>) -> Self {
-        Self { this }
+        Self {
+            this,
+            started: CancellationToken::new(),
+        }
     }
 
-    pub async fn run(mut self, args: A::Args) -> ActorTaskResult {
+    // Cancelled once `Actor::start` has returned successfully
+    pub fn started(&self) -> CancellationToken {
+        self.started.clone()
+    }
+
+    pub async fn run(mut self, args: A::Args) -> Result<ExitReason, ActorTaskError> {
         let mut actor = A::start(&self.this, args)
             .await
             .map_err(ActorTaskError::Start)?;
 
+        self.started.cancel();
+
+        let result = self.run_started(&mut actor).await;
+        actor.post_stop(&self.this).await;
+        result
+    }
+
+    async fn run_started(&mut self, actor: &mut A) -> Result<ExitReason, ActorTaskError> {
+        let limit = self.this.spec.batch.unwrap_or(1);
+        let mut buffer = Vec::with_capacity(limit);
+
         loop {
+            if !self.this.cancel.is_cancelled() {
+                let msgs = self.this.take_unstashed(limit);
+                if !msgs.is_empty() {
+                    self.handle_msgs(actor, msgs).await?;
+                    continue;
+                }
+            }
+
             tokio::select! {
-                signal = self.this.rx.recv() => {
-                    self.handle_signal(&mut actor, signal).await?;
+                count = poll_fn(|cx| {
+                    self.this.rx.lock().unwrap().poll_recv_many(cx, &mut buffer, limit)
+                }) => {
+                    if count == 0 {
+                        self.this.exit();
+                        continue;
+                    }
+                    self.handle_mailbox(actor, &mut buffer).await?;
+                }
+                Some(signal) = self.this.signals.recv() => {
+                    self.handle_signal(actor, Some(signal)).await?;
                 }
                 _ = self.this.cancel.cancelled() => {
-                    self.handle_cancel(&mut actor).await?;
                     break;
                 }
             }
         }
 
+        let reason = self.this.take_exit_reason();
+        self.handle_cancel(actor, &reason).await?;
+        Ok(reason)
+    }
+
+    async fn handle_mailbox(
+        &mut self,
+        actor: &mut A,
+        buffer: &mut Vec<ActorSignal<A::Msg>>,
+    ) -> ActorTaskResult {
+        let signals = buffer.drain(..);
+
+        if self.this.spec.batch.is_some() {
+            let msgs = signals
+                .filter_map(ActorSignal::into_live_msg)
+                .collect::<Vec<_>>();
+            self.handle_msgs(actor, msgs).await?;
+        } else {
+            for signal in signals.collect::<Vec<_>>() {
+                self.handle_signal(actor, Some(signal)).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn handle_msgs(&mut self, actor: &mut A, msgs: Vec<A::Msg>) -> ActorTaskResult {
+        if msgs.is_empty() {
+            return Ok(());
+        }
+        if self.this.spec.batch.is_some() {
+            let result = actor.receive_batch(&self.this, msgs).await;
+            self.handle_result(actor, result).await?;
+        } else {
+            for msg in msgs {
+                let result = actor.receive(&self.this, msg).await;
+                self.handle_result(actor, result).await?;
+            }
+        }
         Ok(())
     }
 
@@ -74,25 +333,67 @@ impl<A: Actor> ActorTask<A> {
         actor: &mut A,
         signal: Option<ActorSignal<A::Msg>>,
     ) -> ActorTaskResult {
-        match signal {
-            Some(signal) => {
-                match signal {
-                    ActorSignal::Msg(msg) => {
-                        actor
-                            .receive(&self.this, msg)
-                            .await
-                            .map_err(ActorTaskError::Receive)?;
+        let result = match signal {
+            Some(signal) => match signal {
+                ActorSignal::Msg(msg) => actor.receive(&self.this, msg).await,
+                ActorSignal::Call(msg, deadline) => {
+                    if Instant::now() < deadline {
+                        actor.receive(&self.this, msg).await
+                    } else {
+                        Ok(())
+                    }
+                }
+                ActorSignal::Timer(timer, msg) => {
+                    if !timer.is_cancelled() {
+                        actor.handle_timer(&self.this, timer, msg).await
+                    } else {
+                        Ok(())
                     }
-                };
+                }
+                ActorSignal::Down(down) => actor.handle_down(&self.this, down).await,
+                ActorSignal::Exit(exit) => {
+                    if self.this.is_trapping_exit() {
+                        actor.handle_exit(&self.this, exit).await
+                    } else if exit.reason.is_abnormal() {
+                        return Err(ActorTaskError::Linked(exit));
+                    } else {
+                        Ok(())
+                    }
+                }
+            },
+            None => {
+                self.this.exit();
+                Ok(())
             }
-            None => self.this.cancel.cancel(),
+        };
+        self.handle_result(actor, result).await
+    }
+
+    async fn handle_result(&mut self, actor: &mut A, result: ActorResult) -> ActorTaskResult {
+        let Err(e) = result else {
+            return Ok(());
+        };
+        match actor.on_receive_error(&self.this, e).await {
+            ErrorAction::Resume => Ok(()),
+            ErrorAction::Restart(e) => {
+                let args = actor
+                    .pre_restart(&self.this, e)
+                    .await
+                    .map_err(ActorTaskError::Receive)?;
+                *actor = A::start(&self.this, args)
+                    .await
+                    .map_err(ActorTaskError::Start)?;
+                Ok(())
+            }
+            ErrorAction::Stop(e) => Err(ActorTaskError::Receive(e)),
         }
-        Ok(())
     }
 
-    async fn handle_cancel(&mut self, actor: &mut A) -> ActorTaskResult {
-        self.this.rx.close();
-        actor.exit().await.map_err(ActorTaskError::Exit)?;
+    async fn handle_cancel(&mut self, actor: &mut A, reason: &ExitReason) -> ActorTaskResult {
+        self.this.rx.lock().unwrap().close();
+        if !matches!(reason, ExitReason::Killed) {
+            actor.exit(reason).await.map_err(ActorTaskError::Exit)?;
+        }
         Ok(())
     }
 }
@@ -1,5 +1,25 @@
 #[macro_export]
 macro_rules! call {
+    ($act:expr, timeout = $timeout:expr, $msg:path) => {{
+        async {
+            $act.call_timeout($timeout, |reply| $msg( reply )).await
+        }
+    }};
+    ($act:expr, timeout = $timeout:expr, $msg:path, $( $arg:expr ),+ $(,)? ) => {{
+        async {
+            $act.call_timeout($timeout, |reply| $msg( $( $arg ),*, reply )).await
+        }
+    }};
+    ($act:expr, deadline = $deadline:expr, $msg:path) => {{
+        async {
+            $act.call_deadline($deadline, |reply| $msg( reply )).await
+        }
+    }};
+    ($act:expr, deadline = $deadline:expr, $msg:path, $( $arg:expr ),+ $(,)? ) => {{
+        async {
+            $act.call_deadline($deadline, |reply| $msg( $( $arg ),*, reply )).await
+        }
+    }};
     ($act:expr, $msg:path) => {{
         async {
             $act.call(|reply| $msg( reply )).await
@@ -14,6 +34,26 @@ macro_rules! call {
 
 #[macro_export]
 macro_rules! try_call {
+    ($act:expr, timeout = $timeout:expr, $msg:path) => {{
+        async {
+            $act.try_call_timeout($timeout, |reply| $msg( reply )).await
+        }
+    }};
+    ($act:expr, timeout = $timeout:expr, $msg:path, $( $arg:expr ),+ $(,)? ) => {{
+        async {
+            $act.try_call_timeout($timeout, |reply| $msg( $( $arg ),*, reply )).await
+        }
+    }};
+    ($act:expr, deadline = $deadline:expr, $msg:path) => {{
+        async {
+            $act.try_call_deadline($deadline, |reply| $msg( reply )).await
+        }
+    }};
+    ($act:expr, deadline = $deadline:expr, $msg:path, $( $arg:expr ),+ $(,)? ) => {{
+        async {
+            $act.try_call_deadline($deadline, |reply| $msg( $( $arg ),*, reply )).await
+        }
+    }};
     ($act:expr, $msg:path) => {{
         async {
             $act.try_call(|reply| $msg( reply )).await
@@ -0,0 +1,181 @@
+use std::io;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+// Wire format for `RpcEnvelope`s. The built-in formats are each behind a cargo feature: `json`
+// (the default), `bincode`, `msgpack` and `cbor`. `RpcServer` and `RpcClient` accept any other
+// implementation in their place.
+pub trait Codec: Copy + Send + Sync + 'static {
+    // Identifies the codec during negotiation, so it must be unique among the codecs a server or
+    // client accepts. 1 to 4 are taken by the built-in codecs.
+    fn id(&self) -> u8;
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>>;
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T>;
+}
+
+#[cfg(not(any(
+    feature = "json",
+    feature = "bincode",
+    feature = "msgpack",
+    feature = "cbor"
+)))]
+compile_error!("at least one of the `json`, `bincode`, `msgpack` or `cbor` features is required");
+
+fn invalid_data<E>(e: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, e)
+}
+
+#[cfg(feature = "json")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonCodec;
+
+#[cfg(feature = "json")]
+impl Codec for JsonCodec {
+    fn id(&self) -> u8 {
+        1
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        serde_json::to_vec(value).map_err(io::Error::other)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        serde_json::from_slice(bytes).map_err(invalid_data)
+    }
+}
+
+#[cfg(feature = "bincode")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl Codec for BincodeCodec {
+    fn id(&self) -> u8 {
+        2
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        bincode::serde::encode_to_vec(value, bincode::config::standard()).map_err(io::Error::other)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(value, _)| value)
+            .map_err(invalid_data)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MsgPackCodec;
+
+#[cfg(feature = "msgpack")]
+impl Codec for MsgPackCodec {
+    fn id(&self) -> u8 {
+        3
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        rmp_serde::to_vec(value).map_err(io::Error::other)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        rmp_serde::from_slice(bytes).map_err(invalid_data)
+    }
+}
+
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl Codec for CborCodec {
+    fn id(&self) -> u8 {
+        4
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).map_err(io::Error::other)?;
+        Ok(bytes)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        ciborium::from_reader(bytes).map_err(invalid_data)
+    }
+}
+
+// The codecs compiled in, for choosing one per connection. Clients offer the codecs they accept
+// in order of preference and the server picks the first one it also accepts.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodecKind {
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "bincode")]
+    Bincode,
+    #[cfg(feature = "msgpack")]
+    MsgPack,
+    #[cfg(feature = "cbor")]
+    Cbor,
+}
+
+impl CodecKind {
+    // Every compiled in codec, most compact first
+    pub const ALL: &[CodecKind] = &[
+        #[cfg(feature = "bincode")]
+        CodecKind::Bincode,
+        #[cfg(feature = "msgpack")]
+        CodecKind::MsgPack,
+        #[cfg(feature = "cbor")]
+        CodecKind::Cbor,
+        #[cfg(feature = "json")]
+        CodecKind::Json,
+    ];
+}
+
+impl Codec for CodecKind {
+    fn id(&self) -> u8 {
+        match self {
+            #[cfg(feature = "json")]
+            CodecKind::Json => JsonCodec.id(),
+            #[cfg(feature = "bincode")]
+            CodecKind::Bincode => BincodeCodec.id(),
+            #[cfg(feature = "msgpack")]
+            CodecKind::MsgPack => MsgPackCodec.id(),
+            #[cfg(feature = "cbor")]
+            CodecKind::Cbor => CborCodec.id(),
+        }
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "json")]
+            CodecKind::Json => JsonCodec.encode(value),
+            #[cfg(feature = "bincode")]
+            CodecKind::Bincode => BincodeCodec.encode(value),
+            #[cfg(feature = "msgpack")]
+            CodecKind::MsgPack => MsgPackCodec.encode(value),
+            #[cfg(feature = "cbor")]
+            CodecKind::Cbor => CborCodec.encode(value),
+        }
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        match self {
+            #[cfg(feature = "json")]
+            CodecKind::Json => JsonCodec.decode(bytes),
+            #[cfg(feature = "bincode")]
+            CodecKind::Bincode => BincodeCodec.decode(bytes),
+            #[cfg(feature = "msgpack")]
+            CodecKind::MsgPack => MsgPackCodec.decode(bytes),
+            #[cfg(feature = "cbor")]
+            CodecKind::Cbor => CborCodec.decode(bytes),
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+
+use crate::{Act, Actor, ActorResult, ActorSelf, CallError, ExitReason, Reply, SendError};
+
+// What the bus does when a subscriber's mailbox is full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SubscriberPolicy {
+    // Wait for space, holding up delivery to every later subscriber
+    #[default]
+    Block,
+    // Skip the event for this subscriber
+    Drop,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionRef(u64);
+
+// Type-erased subscriber, so that one bus can deliver to actors with different message types
+trait EventSink<E>: Send + Sync {
+    fn deliver<'a>(
+        &'a self,
+        event: E,
+        policy: SubscriberPolicy,
+    ) -> BoxFuture<'a, Result<(), SendError<()>>>;
+}
+
+impl<E, M> EventSink<E> for Act<M>
+where
+    E: Send + 'static,
+    M: From<E> + Send + 'static,
+{
+    fn deliver<'a>(
+        &'a self,
+        event: E,
+        policy: SubscriberPolicy,
+    ) -> BoxFuture<'a, Result<(), SendError<()>>> {
+        Box::pin(async move {
+            let result = match policy {
+                SubscriberPolicy::Block => self.cast(event.into()).await,
+                SubscriberPolicy::Drop => self.try_cast(event.into()),
+            };
+            result.map_err(|e| match e {
+                SendError::Full(_) => SendError::Full(()),
+                SendError::Closed(_) => SendError::Closed(()),
+            })
+        })
+    }
+}
+
+pub struct Subscription<E> {
+    sink: Box<dyn EventSink<E>>,
+    filter: Box<dyn Fn(&E) -> bool + Send + Sync>,
+    policy: SubscriberPolicy,
+}
+
+impl<E: Send + 'static> Subscription<E> {
+    // Events are converted into the subscriber's own message type with `From`
+    pub fn new<M>(act: Act<M>) -> Self
+    where
+        M: From<E> + Send + 'static,
+    {
+        Self {
+            sink: Box::new(act),
+            filter: Box::new(|_| true),
+            policy: SubscriberPolicy::default(),
+        }
+    }
+
+    pub fn filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Box::new(filter);
+        self
+    }
+
+    pub fn policy(mut self, policy: SubscriberPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+pub enum EventBusMsg<E> {
+    Subscribe(Subscription<E>, Reply<SubscriptionRef>),
+    Unsubscribe(SubscriptionRef),
+    Publish(E),
+}
+
+pub struct EventBus<E> {
+    next_id: u64,
+    subscriptions: Vec<(SubscriptionRef, Subscription<E>)>,
+    _event: PhantomData<fn(E)>,
+}
+
+#[async_trait]
+impl<E: Clone + Send + 'static> Actor for EventBus<E> {
+    type Args = ();
+    type Msg = EventBusMsg<E>;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self {
+            next_id: 0,
+            subscriptions: vec![],
+            _event: PhantomData,
+        })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            EventBusMsg::Subscribe(subscription, reply) => {
+                let id = SubscriptionRef(self.next_id);
+                self.next_id += 1;
+                self.subscriptions.push((id, subscription));
+                let _ = reply.send(id);
+            }
+            EventBusMsg::Unsubscribe(id) => {
+                self.subscriptions.retain(|(other, _)| *other != id);
+            }
+            EventBusMsg::Publish(event) => {
+                let mut closed = vec![];
+                for (id, subscription) in &self.subscriptions {
+                    if !(subscription.filter)(&event) {
+                        continue;
+                    }
+                    let result = subscription
+                        .sink
+                        .deliver(event.clone(), subscription.policy)
+                        .await;
+                    if let Err(SendError::Closed(())) = result {
+                        closed.push(*id);
+                    }
+                }
+                // Subscribers that have stopped are dropped
+                self.subscriptions.retain(|(id, _)| !closed.contains(id));
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+impl<E: Clone + Send + 'static> Act<EventBusMsg<E>> {
+    pub async fn subscribe<M, F>(
+        &self,
+        act: Act<M>,
+        filter: F,
+    ) -> Result<SubscriptionRef, CallError<EventBusMsg<E>>>
+    where
+        M: From<E> + Send + 'static,
+        F: Fn(&E) -> bool + Send + Sync + 'static,
+    {
+        self.subscribe_with(Subscription::new(act).filter(filter))
+            .await
+    }
+
+    pub async fn subscribe_with(
+        &self,
+        subscription: Subscription<E>,
+    ) -> Result<SubscriptionRef, CallError<EventBusMsg<E>>> {
+        self.call(|reply| EventBusMsg::Subscribe(subscription, reply))
+            .await
+    }
+
+    pub async fn unsubscribe(&self, id: SubscriptionRef) -> Result<(), SendError<EventBusMsg<E>>> {
+        self.cast(EventBusMsg::Unsubscribe(id)).await
+    }
+
+    pub async fn publish(&self, event: E) -> Result<(), SendError<EventBusMsg<E>>> {
+        self.cast(EventBusMsg::Publish(event)).await
+    }
+}
@@ -0,0 +1,35 @@
+use std::{error::Error, sync::Arc};
+
+// Why an actor stopped. Shared with monitors and linked actors, so custom errors are kept behind
+// an `Arc`
+#[derive(Debug, Clone)]
+pub enum ExitReason {
+    // The actor stopped itself, or every handle to it was dropped
+    Normal,
+    // The actor was asked to stop by its scope or supervisor
+    Shutdown,
+    Custom(Arc<dyn Error + Send + Sync>),
+    // The actor stopped immediately, without running `Actor::exit`
+    Killed,
+}
+
+impl ExitReason {
+    pub fn custom(e: impl Error + Send + Sync + 'static) -> Self {
+        ExitReason::Custom(Arc::new(e))
+    }
+
+    pub fn is_normal(&self) -> bool {
+        matches!(self, ExitReason::Normal | ExitReason::Shutdown)
+    }
+}
+
+impl std::fmt::Display for ExitReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExitReason::Normal => write!(f, "normal"),
+            ExitReason::Shutdown => write!(f, "shutdown"),
+            ExitReason::Custom(e) => write!(f, "{e}"),
+            ExitReason::Killed => write!(f, "killed"),
+        }
+    }
+}
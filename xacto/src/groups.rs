@@ -0,0 +1,156 @@
+use std::{
+    any::{Any, TypeId},
+    collections::{HashMap, HashSet},
+};
+
+use crate::{Act, ActorError, ActorId, RegistryError, Reply};
+
+struct Group {
+    type_id: TypeId,
+    members: Vec<(ActorId, Box<dyn Any + Send>)>,
+    next: usize,
+}
+
+// Named groups of actors with the same message type, like Erlang's `pg`
+#[derive(Default)]
+pub(crate) struct Groups {
+    groups: HashMap<String, Group>,
+    // actor -> groups it has joined
+    actors: HashMap<ActorId, HashSet<String>>,
+}
+
+impl Groups {
+    pub(crate) fn join<Msg: Send + 'static>(
+        &mut self,
+        name: String,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        let id = act.id();
+        let group = self.groups.entry(name.clone()).or_insert_with(|| Group {
+            type_id: TypeId::of::<Act<Msg>>(),
+            members: vec![],
+            next: 0,
+        });
+        if group.type_id != TypeId::of::<Act<Msg>>() {
+            return Err(RegistryError::WrongType(name));
+        }
+        if group.members.iter().any(|(member, _)| *member == id) {
+            return Ok(());
+        }
+        group.members.push((id, Box::new(act)));
+        self.actors.entry(id).or_default().insert(name);
+        Ok(())
+    }
+
+    pub(crate) fn leave(&mut self, name: &str, id: ActorId) {
+        if let Some(group) = self.groups.get_mut(name) {
+            group.members.retain(|(member, _)| *member != id);
+            if group.members.is_empty() {
+                self.groups.remove(name);
+            }
+        }
+        if let Some(names) = self.actors.get_mut(&id) {
+            names.remove(name);
+            if names.is_empty() {
+                self.actors.remove(&id);
+            }
+        }
+    }
+
+    pub(crate) fn leave_all(&mut self, id: ActorId) {
+        for name in self.actors.remove(&id).into_iter().flatten() {
+            if let Some(group) = self.groups.get_mut(&name) {
+                group.members.retain(|(member, _)| *member != id);
+                if group.members.is_empty() {
+                    self.groups.remove(&name);
+                }
+            }
+        }
+    }
+
+    // Each call starts from the next member, so that `GroupMembers::call_any` takes turns
+    pub(crate) fn members<Msg: Send + 'static>(
+        &mut self,
+        name: &str,
+    ) -> Result<GroupMembers<Msg>, RegistryError> {
+        let Some(group) = self.groups.get_mut(name) else {
+            return Ok(GroupMembers {
+                name: name.to_string(),
+                members: vec![],
+            });
+        };
+        if group.type_id != TypeId::of::<Act<Msg>>() {
+            return Err(RegistryError::WrongType(name.to_string()));
+        }
+
+        let start = group.next % group.members.len();
+        group.next = start + 1;
+        let members = group.members[start..]
+            .iter()
+            .chain(&group.members[..start])
+            .filter_map(|(_, act)| act.downcast_ref::<Act<Msg>>().cloned())
+            .collect();
+
+        Ok(GroupMembers {
+            name: name.to_string(),
+            members,
+        })
+    }
+}
+
+// Snapshot of a group's members when it was looked up
+pub struct GroupMembers<Msg> {
+    name: String,
+    members: Vec<Act<Msg>>,
+}
+
+impl<Msg: Send + 'static> GroupMembers<Msg> {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn acts(&self) -> &[Act<Msg>] {
+        &self.members
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    // Returns how many members the message was delivered to. Members that have stopped since the
+    // lookup are skipped. Taking anything that converts into `Msg` allows broadcasting to actors
+    // whose own message type can't be cloned, e.g. because it carries a `Reply`.
+    pub async fn broadcast<N>(&self, msg: N) -> usize
+    where
+        N: Clone + Into<Msg>,
+    {
+        let mut sent = 0;
+        for act in &self.members {
+            if act.cast(msg.clone().into()).await.is_ok() {
+                sent += 1;
+            }
+        }
+        sent
+    }
+
+    // Calls the members in turn until one replies. Members that have stopped since the lookup,
+    // or stop before replying, are skipped. Fails with the last member's error if none replied.
+    pub async fn call_any<T, F>(&self, f: F) -> Result<T, ActorError>
+    where
+        T: Send + 'static,
+        F: Fn(Reply<T>) -> Msg,
+    {
+        let mut error = ActorError::from(RegistryError::EmptyGroup(self.name.clone()));
+        for act in &self.members {
+            match act.call(&f).await {
+                Ok(value) => return Ok(value),
+                Err(e) => error = e.into(),
+            }
+        }
+        Err(error)
+    }
+}
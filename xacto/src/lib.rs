@@ -1,19 +1,44 @@
 mod act;
 mod actor;
 mod actor_error;
+mod actor_spec;
 mod actor_task;
 mod call;
+mod codec;
+mod event_bus;
+mod exit_reason;
+mod groups;
 mod link;
+mod mailbox;
+mod monitor;
+mod pool;
+mod recipient;
+mod registry;
 mod reply_map;
+mod rpc;
 mod rpc_message;
 mod scope;
+mod supervisor;
+mod timer;
+mod transport;
 
 pub use act::*;
 pub use actor::*;
 pub use actor_error::*;
+pub use actor_spec::*;
 pub use actor_task::*;
-pub use call::*;
+pub use codec::*;
+pub use event_bus::*;
+pub use exit_reason::*;
+pub use groups::*;
 pub use link::*;
+pub use monitor::*;
+pub use pool::*;
+pub use recipient::*;
 pub use reply_map::*;
+pub use rpc::*;
 pub use rpc_message::*;
 pub use scope::*;
+pub use supervisor::*;
+pub use timer::*;
+pub use transport::*;
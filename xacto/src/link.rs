@@ -1,8 +1,11 @@
-use std::sync::{Arc, RwLock};
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
-use tokio::sync::watch;
+use tokio::{sync::watch, time::Instant};
 
-use crate::{Act, LinkError, Reply};
+use crate::{Act, CallError, LinkError, RecvError, Reply};
 
 pub struct LinkPublisher<Msg> {
     tx: watch::Sender<Option<Act<Msg>>>,
@@ -19,6 +22,10 @@ impl<Msg> LinkPublisher<Msg> {
     pub fn update(&self, act: Act<Msg>) {
         let _ = self.tx.send(Some(act));
     }
+
+    pub fn clear(&self) {
+        let _ = self.tx.send(None);
+    }
 }
 
 #[derive(Clone)]
@@ -47,16 +54,17 @@ impl<Msg> Link<Msg> {
     }
 
     pub async fn get(&self) -> Result<Act<Msg>, LinkError<(), Msg>> {
+        // Once anything has been published, a `None` means the actor is down until the next
+        // `update`
         if let Ok(true) = self.rx.has_changed() {
             let mut rx = self.rx.clone();
-            if let Some(act) = rx.borrow_and_update().as_ref() {
-                let mut local = self.local.write().map_err(|e| {
-                    eprintln!("lock poisoned: {e:?}");
-                    LinkError::Unavailable(())
-                })?;
-                *local = Some(act.clone());
-                return Ok(act.clone());
-            }
+            let current = rx.borrow_and_update().clone();
+            let mut local = self.local.write().map_err(|e| {
+                eprintln!("lock poisoned: {e:?}");
+                LinkError::Unavailable(())
+            })?;
+            *local = current.clone();
+            return current.ok_or(LinkError::Unavailable(()));
         }
 
         {
@@ -88,6 +96,14 @@ impl<Msg> Link<Msg> {
         Ok(act)
     }
 
+    // Like `get`, but gives up waiting for an actor to be published at `deadline`
+    async fn get_deadline(&self, deadline: Instant) -> Result<Act<Msg>, LinkError<(), Msg>> {
+        match tokio::time::timeout_at(deadline, self.get()).await {
+            Ok(result) => result,
+            Err(_) => Err(LinkError::Call(CallError::Recv(RecvError::Timeout))),
+        }
+    }
+
     pub async fn cast(&self, msg: Msg) -> Result<(), LinkError<Msg, Msg>> {
         match self.get_with_msg(msg).await {
             Ok((msg, act)) => act.cast(msg).await.map_err(|e| LinkError::Send(e)),
@@ -123,4 +139,60 @@ impl<Msg> Link<Msg> {
             Err(e) => Err(e),
         }
     }
+
+    pub async fn call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, LinkError<(), Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        self.call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn call_deadline<T, F>(
+        &self,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, LinkError<(), Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        match self.get_deadline(deadline).await {
+            Ok(act) => act
+                .call_deadline(deadline, f)
+                .await
+                .map_err(|e| LinkError::Call(e)),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn try_call_timeout<T, F>(
+        &self,
+        timeout: Duration,
+        f: F,
+    ) -> Result<T, LinkError<(), Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        self.try_call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn try_call_deadline<T, F>(
+        &self,
+        deadline: Instant,
+        f: F,
+    ) -> Result<T, LinkError<(), Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        match self.get_deadline(deadline).await {
+            Ok(act) => act
+                .try_call_deadline(deadline, f)
+                .await
+                .map_err(|e| LinkError::Call(e)),
+            Err(e) => Err(e),
+        }
+    }
 }
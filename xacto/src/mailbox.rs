@@ -0,0 +1,107 @@
+use std::{
+    sync::{Arc, Mutex, Weak},
+    task::{Context, Poll},
+};
+
+use tokio::sync::mpsc;
+
+use crate::{ActorSignal, ActorSpec, Mailbox};
+
+pub(crate) enum MailboxTx<M> {
+    Bounded(mpsc::Sender<ActorSignal<M>>),
+    Unbounded(mpsc::UnboundedSender<ActorSignal<M>>),
+}
+
+impl<M> MailboxTx<M> {
+    pub(crate) fn downgrade(&self) -> WeakMailboxTx<M> {
+        match self {
+            MailboxTx::Bounded(tx) => WeakMailboxTx::Bounded(tx.downgrade()),
+            MailboxTx::Unbounded(tx) => WeakMailboxTx::Unbounded(tx.downgrade()),
+        }
+    }
+}
+
+impl<M> Clone for MailboxTx<M> {
+    fn clone(&self) -> Self {
+        match self {
+            MailboxTx::Bounded(tx) => MailboxTx::Bounded(tx.clone()),
+            MailboxTx::Unbounded(tx) => MailboxTx::Unbounded(tx.clone()),
+        }
+    }
+}
+
+pub(crate) enum WeakMailboxTx<M> {
+    Bounded(mpsc::WeakSender<ActorSignal<M>>),
+    Unbounded(mpsc::WeakUnboundedSender<ActorSignal<M>>),
+}
+
+impl<M> WeakMailboxTx<M> {
+    pub(crate) fn upgrade(&self) -> Option<MailboxTx<M>> {
+        match self {
+            WeakMailboxTx::Bounded(tx) => tx.upgrade().map(MailboxTx::Bounded),
+            WeakMailboxTx::Unbounded(tx) => tx.upgrade().map(MailboxTx::Unbounded),
+        }
+    }
+}
+
+impl<M> Clone for WeakMailboxTx<M> {
+    fn clone(&self) -> Self {
+        match self {
+            WeakMailboxTx::Bounded(tx) => WeakMailboxTx::Bounded(tx.clone()),
+            WeakMailboxTx::Unbounded(tx) => WeakMailboxTx::Unbounded(tx.clone()),
+        }
+    }
+}
+
+pub(crate) enum MailboxRx<M> {
+    Bounded(mpsc::Receiver<ActorSignal<M>>),
+    Unbounded(mpsc::UnboundedReceiver<ActorSignal<M>>),
+}
+
+impl<M> MailboxRx<M> {
+    pub(crate) fn poll_recv_many(
+        &mut self,
+        cx: &mut Context<'_>,
+        buffer: &mut Vec<ActorSignal<M>>,
+        limit: usize,
+    ) -> Poll<usize> {
+        match self {
+            MailboxRx::Bounded(rx) => rx.poll_recv_many(cx, buffer, limit),
+            MailboxRx::Unbounded(rx) => rx.poll_recv_many(cx, buffer, limit),
+        }
+    }
+
+    pub(crate) fn try_recv(&mut self) -> Option<ActorSignal<M>> {
+        match self {
+            MailboxRx::Bounded(rx) => rx.try_recv().ok(),
+            MailboxRx::Unbounded(rx) => rx.try_recv().ok(),
+        }
+    }
+
+    pub(crate) fn close(&mut self) {
+        match self {
+            MailboxRx::Bounded(rx) => rx.close(),
+            MailboxRx::Unbounded(rx) => rx.close(),
+        }
+    }
+}
+
+// The receiving end is shared with the actor's handles so that senders can evict the oldest
+// message from a full `Overflow::DropOldest` mailbox. Handles only hold it weakly, so it is still
+// dropped along with the actor.
+pub(crate) type SharedMailboxRx<M> = Arc<Mutex<MailboxRx<M>>>;
+pub(crate) type WeakMailboxRx<M> = Weak<Mutex<MailboxRx<M>>>;
+
+pub(crate) fn channel<M>(spec: &ActorSpec) -> (MailboxTx<M>, SharedMailboxRx<M>) {
+    let (tx, rx) = match spec.mailbox {
+        Mailbox::Bounded(capacity) => {
+            let (tx, rx) = mpsc::channel(capacity);
+            (MailboxTx::Bounded(tx), MailboxRx::Bounded(rx))
+        }
+        Mailbox::Unbounded => {
+            let (tx, rx) = mpsc::unbounded_channel();
+            (MailboxTx::Unbounded(tx), MailboxRx::Unbounded(rx))
+        }
+    };
+    (tx, Arc::new(Mutex::new(rx)))
+}
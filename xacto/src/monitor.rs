@@ -0,0 +1,84 @@
+use tokio::sync::mpsc;
+
+use crate::{ActorId, ActorOutput, ActorSignal, ExitReason};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MonitorRef(u64);
+
+impl MonitorRef {
+    pub(crate) fn new(id: u64) -> Self {
+        Self(id)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Down {
+    pub monitor: MonitorRef,
+    pub id: ActorId,
+    pub reason: DownReason,
+}
+
+#[derive(Debug, Clone)]
+pub enum DownReason {
+    Exited(ExitReason),
+    Failed(String),
+    Aborted,
+    Panicked(Option<String>),
+    Unknown(String),
+    // The target was not running when the monitor was created
+    NoProc,
+}
+
+impl DownReason {
+    pub fn is_abnormal(&self) -> bool {
+        match self {
+            DownReason::Exited(reason) => !reason.is_normal(),
+            DownReason::Failed(_) | DownReason::Panicked(_) | DownReason::Unknown(_) => true,
+            DownReason::Aborted | DownReason::NoProc => false,
+        }
+    }
+}
+
+impl From<&ActorOutput> for DownReason {
+    fn from(output: &ActorOutput) -> Self {
+        match output {
+            ActorOutput::Exited(reason) => DownReason::Exited(reason.clone()),
+            ActorOutput::Failed(e) => DownReason::Failed(format!("{e:?}")),
+            ActorOutput::Aborted => DownReason::Aborted,
+            ActorOutput::Panicked(payload) => {
+                let message = payload.as_ref().and_then(|payload| {
+                    if let Some(s) = payload.downcast_ref::<&str>() {
+                        Some(s.to_string())
+                    } else {
+                        payload.downcast_ref::<String>().cloned()
+                    }
+                });
+                DownReason::Panicked(message)
+            }
+            ActorOutput::Unknown(e) => DownReason::Unknown(e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExitSignal {
+    pub from: ActorId,
+    pub reason: DownReason,
+}
+
+// Type-erased handle to an actor's signal channel, so the `ScopeContext` can deliver signals to
+// actors regardless of their message type
+pub(crate) trait SignalSender: Send {
+    fn send_down(&self, down: Down);
+    fn send_exit(&self, exit: ExitSignal);
+}
+
+impl<M: Send + 'static> SignalSender for mpsc::UnboundedSender<ActorSignal<M>> {
+    fn send_down(&self, down: Down) {
+        let _ = self.send(ActorSignal::Down(down));
+    }
+
+    fn send_exit(&self, exit: ExitSignal) {
+        let _ = self.send(ActorSignal::Exit(exit));
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    hash::{BuildHasher, DefaultHasher, Hash, Hasher, RandomState},
+    sync::{
+        Arc,
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use tokio::sync::oneshot;
+
+use crate::{
+    Act, Actor, ActorId, ActorSpec, CallError, Link, LinkError, LinkPublisher, Reply,
+    RestartStrategy, Scope, SupervisorMsg, SupervisorSpec, WorkerSpec, recv_reply,
+};
+
+pub enum Routing<Msg> {
+    RoundRobin,
+    Random,
+    // The worker with the most free space in its mailbox
+    LeastLoaded,
+    // Messages with the same key always go to the same worker
+    ConsistentHash(Arc<dyn Fn(&Msg) -> u64 + Send + Sync>),
+}
+
+impl<Msg> Routing<Msg> {
+    pub fn consistent_hash<K, F>(key: F) -> Self
+    where
+        K: Hash,
+        F: Fn(&Msg) -> K + Send + Sync + 'static,
+    {
+        Routing::ConsistentHash(Arc::new(move |msg| {
+            let mut hasher = DefaultHasher::new();
+            key(msg).hash(&mut hasher);
+            hasher.finish()
+        }))
+    }
+}
+
+impl<Msg> Clone for Routing<Msg> {
+    fn clone(&self) -> Self {
+        match self {
+            Routing::RoundRobin => Routing::RoundRobin,
+            Routing::Random => Routing::Random,
+            Routing::LeastLoaded => Routing::LeastLoaded,
+            Routing::ConsistentHash(key) => Routing::ConsistentHash(key.clone()),
+        }
+    }
+}
+
+// A fixed number of identical workers under a one-for-one supervisor, so that crashed workers are
+// replaced in the same slot
+pub struct ActorPool<A: Actor> {
+    size: usize,
+    args: Arc<dyn Fn(usize) -> A::Args + Send + Sync>,
+    routing: Routing<A::Msg>,
+    spec: ActorSpec,
+    max_restarts: usize,
+    period: Duration,
+}
+
+impl<A: Actor> ActorPool<A> {
+    // `args` is called with the worker's index whenever that worker is started
+    pub fn new<F>(size: usize, args: F) -> Self
+    where
+        F: Fn(usize) -> A::Args + Send + Sync + 'static,
+    {
+        assert!(size > 0, "pool size must be greater than 0");
+        Self {
+            size,
+            args: Arc::new(args),
+            routing: Routing::RoundRobin,
+            spec: ActorSpec::default(),
+            max_restarts: 3,
+            period: Duration::from_secs(5),
+        }
+    }
+
+    pub fn routing(mut self, routing: Routing<A::Msg>) -> Self {
+        self.routing = routing;
+        self
+    }
+
+    pub fn spec(mut self, spec: ActorSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    pub fn intensity(mut self, max_restarts: usize, period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.period = period;
+        self
+    }
+
+    pub(crate) async fn start(self, scope: &mut Scope) -> PoolRef<A::Msg> {
+        let mut tree = SupervisorSpec::new(RestartStrategy::OneForOne)
+            .intensity(self.max_restarts, self.period);
+        let mut workers = Vec::with_capacity(self.size);
+
+        for i in 0..self.size {
+            let (publisher, link) = LinkPublisher::new();
+            let args = self.args.clone();
+            let worker = WorkerSpec::<A>::new(format!("worker-{i}"), move || args(i))
+                .spec(self.spec)
+                .publish(publisher);
+            tree = tree.child(worker);
+            workers.push(link);
+        }
+
+        let supervisor = scope.start_tree(tree).await;
+        // Only the seed comes from `RandomState`, whose keys are randomly generated. xorshift gets
+        // stuck at 0.
+        let seed = RandomState::new().hash_one(supervisor.id()) | 1;
+
+        PoolRef {
+            supervisor,
+            workers: workers.into(),
+            routing: self.routing,
+            next: Arc::new(AtomicUsize::new(0)),
+            rng: Arc::new(AtomicU64::new(seed)),
+        }
+    }
+}
+
+pub struct PoolRef<Msg> {
+    supervisor: Act<SupervisorMsg>,
+    workers: Arc<[Link<Msg>]>,
+    routing: Routing<Msg>,
+    next: Arc<AtomicUsize>,
+    // xorshift64* state for `Routing::Random`
+    rng: Arc<AtomicU64>,
+}
+
+impl<Msg> Clone for PoolRef<Msg> {
+    fn clone(&self) -> Self {
+        Self {
+            supervisor: self.supervisor.clone(),
+            workers: self.workers.clone(),
+            routing: self.routing.clone(),
+            next: self.next.clone(),
+            rng: self.rng.clone(),
+        }
+    }
+}
+
+impl<Msg> PoolRef<Msg> {
+    // The pool's supervisor, which stops every worker when it exits
+    pub fn id(&self) -> ActorId {
+        self.supervisor.id()
+    }
+
+    pub fn size(&self) -> usize {
+        self.workers.len()
+    }
+
+    fn next_random(&self) -> u64 {
+        let xorshift = |mut x: u64| {
+            x ^= x >> 12;
+            x ^= x << 25;
+            x ^= x >> 27;
+            x
+        };
+        let prev = self
+            .rng
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(xorshift(x)))
+            .unwrap();
+        xorshift(prev).wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+
+    async fn available(&self, index: usize) -> Option<Act<Msg>> {
+        self.workers[index].get().await.ok()
+    }
+
+    // Workers that are being restarted are skipped, except with consistent hashing
+    async fn route(&self, msg: &Msg) -> Option<Act<Msg>> {
+        let len = self.workers.len();
+        let start = match &self.routing {
+            Routing::RoundRobin => self.next.fetch_add(1, Ordering::Relaxed) % len,
+            Routing::Random => (self.next_random() % len as u64) as usize,
+            Routing::LeastLoaded => {
+                let mut best: Option<Act<Msg>> = None;
+                for i in 0..len {
+                    if let Some(act) = self.available(i).await
+                        && best.as_ref().is_none_or(|b| act.capacity() > b.capacity())
+                    {
+                        best = Some(act);
+                    }
+                }
+                return best;
+            }
+            Routing::ConsistentHash(key) => {
+                return self.available(jump_hash(key(msg), len)).await;
+            }
+        };
+
+        for i in 0..len {
+            if let Some(act) = self.available((start + i) % len).await {
+                return Some(act);
+            }
+        }
+        None
+    }
+
+    pub async fn cast(&self, msg: Msg) -> Result<(), LinkError<Msg, Msg>> {
+        match self.route(&msg).await {
+            Some(act) => act.cast(msg).await.map_err(LinkError::Send),
+            None => Err(LinkError::Unavailable(msg)),
+        }
+    }
+
+    pub async fn try_cast(&self, msg: Msg) -> Result<(), LinkError<Msg, Msg>> {
+        match self.route(&msg).await {
+            Some(act) => act.try_cast(msg).map_err(LinkError::Send),
+            None => Err(LinkError::Unavailable(msg)),
+        }
+    }
+
+    pub async fn call<T, F>(&self, f: F) -> Result<T, LinkError<(), Msg>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> Msg,
+    {
+        // The message is built up front, since consistent hashing routes on its contents
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+        let Some(act) = self.route(&msg).await else {
+            return Err(LinkError::Unavailable(()));
+        };
+        act.cast(msg)
+            .await
+            .map_err(|e| LinkError::Call(CallError::Send(e)))?;
+        recv_reply(rx, None)
+            .await
+            .map_err(|e| LinkError::Call(CallError::Recv(e)))
+    }
+}
+
+// Lamping and Veach's jump consistent hash, which moves as few keys as possible between buckets
+// when the number of buckets changes
+fn jump_hash(mut key: u64, buckets: usize) -> usize {
+    let (mut b, mut j) = (-1i64, 0i64);
+    while j < buckets as i64 {
+        b = j;
+        key = key.wrapping_mul(2862933555777941757).wrapping_add(1);
+        j = ((b + 1) as f64 * ((1u64 << 31) as f64 / ((key >> 33) + 1) as f64)) as i64;
+    }
+    b as usize
+}
+
+impl<Msg> std::fmt::Debug for PoolRef<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "PoolRef({:?})", self.supervisor.id())
+    }
+}
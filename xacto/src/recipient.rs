@@ -0,0 +1,165 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::future::BoxFuture;
+use tokio::{sync::oneshot, time::Instant};
+
+use crate::{Act, ActorId, ActorSignal, CallError, RecvError, Reply, SendError, recv_reply};
+
+// Type-erased `Act` together with the mapping from `N` into its message type. Messages with a
+// deadline are sent as calls, so that the actor can skip them once nobody waits for the reply.
+trait Sink<N>: Send + Sync {
+    fn id(&self) -> ActorId;
+
+    fn send(&self, msg: N, deadline: Option<Instant>) -> BoxFuture<'_, Result<(), SendError<N>>>;
+
+    fn try_send(&self, msg: N, deadline: Option<Instant>) -> Result<(), SendError<N>>;
+}
+
+struct Mapped<Msg, F> {
+    act: Act<Msg>,
+    f: F,
+}
+
+impl<Msg, N, F> Sink<N> for Mapped<Msg, F>
+where
+    Msg: Send + 'static,
+    N: Send + 'static,
+    F: Fn(N) -> Msg + Send + Sync,
+{
+    fn id(&self) -> ActorId {
+        self.act.id()
+    }
+
+    fn send(&self, msg: N, deadline: Option<Instant>) -> BoxFuture<'_, Result<(), SendError<N>>> {
+        Box::pin(
+            self.act
+                .send_mapped(msg, move |msg| signal((self.f)(msg), deadline)),
+        )
+    }
+
+    fn try_send(&self, msg: N, deadline: Option<Instant>) -> Result<(), SendError<N>> {
+        self.act
+            .try_send_mapped(msg, |msg| signal((self.f)(msg), deadline))
+    }
+}
+
+fn signal<Msg>(msg: Msg, deadline: Option<Instant>) -> ActorSignal<Msg> {
+    match deadline {
+        Some(deadline) => ActorSignal::Call(msg, deadline),
+        None => ActorSignal::Msg(msg),
+    }
+}
+
+// A handle that accepts any message type that can be mapped into the actor's own, so that
+// components can be given a narrower view of an actor. Failed sends hand back the unmapped
+// message.
+pub struct Recipient<N> {
+    sink: Arc<dyn Sink<N>>,
+}
+
+impl<N: Send + 'static> Recipient<N> {
+    pub(crate) fn new<Msg, F>(act: Act<Msg>, f: F) -> Self
+    where
+        Msg: Send + 'static,
+        F: Fn(N) -> Msg + Send + Sync + 'static,
+    {
+        Self {
+            sink: Arc::new(Mapped { act, f }),
+        }
+    }
+
+    pub fn id(&self) -> ActorId {
+        self.sink.id()
+    }
+
+    pub async fn cast(&self, msg: N) -> Result<(), SendError<N>> {
+        self.sink.send(msg, None).await
+    }
+
+    pub fn try_cast(&self, msg: N) -> Result<(), SendError<N>> {
+        self.sink.try_send(msg, None)
+    }
+
+    pub async fn call<T, F>(&self, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.cast(f(Reply::new(tx)))
+            .await
+            .map_err(CallError::Send)?;
+        recv_reply(rx, None).await.map_err(CallError::Recv)
+    }
+
+    pub async fn call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        self.call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn call_deadline<T, F>(&self, deadline: Instant, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        let (tx, rx) = oneshot::channel();
+        let send = self.sink.send(f(Reply::new(tx)), Some(deadline));
+        match tokio::time::timeout_at(deadline, send).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(CallError::Send(e)),
+            Err(_) => return Err(CallError::Recv(RecvError::Timeout)),
+        }
+        recv_reply(rx, Some(deadline))
+            .await
+            .map_err(CallError::Recv)
+    }
+
+    pub async fn try_call<T, F>(&self, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.try_cast(f(Reply::new(tx))).map_err(CallError::Send)?;
+        recv_reply(rx, None).await.map_err(CallError::Recv)
+    }
+
+    pub async fn try_call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        self.try_call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn try_call_deadline<T, F>(&self, deadline: Instant, f: F) -> Result<T, CallError<N>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> N,
+    {
+        let (tx, rx) = oneshot::channel();
+        self.sink
+            .try_send(f(Reply::new(tx)), Some(deadline))
+            .map_err(CallError::Send)?;
+        recv_reply(rx, Some(deadline))
+            .await
+            .map_err(CallError::Recv)
+    }
+}
+
+impl<N> Clone for Recipient<N> {
+    fn clone(&self) -> Self {
+        Self {
+            sink: self.sink.clone(),
+        }
+    }
+}
+
+impl<N> std::fmt::Debug for Recipient<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Recipient({:?})", self.sink.id())
+    }
+}
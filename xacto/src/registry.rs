@@ -0,0 +1,79 @@
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+};
+
+use crate::{Act, ActorId, RegistryError};
+
+struct Registered {
+    id: ActorId,
+    act: Box<dyn Any + Send>,
+}
+
+// Names of running actors. Registered actors stay reachable through their name, so their
+// mailbox isn't closed by dropping every other `Act` to them.
+#[derive(Default)]
+pub(crate) struct Registry {
+    names: HashMap<String, Registered>,
+    // actor -> names it's registered under
+    actors: HashMap<ActorId, HashSet<String>>,
+}
+
+impl Registry {
+    pub(crate) fn register<Msg: Send + 'static>(
+        &mut self,
+        name: String,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        if let Some(registered) = self.names.get(&name) {
+            return Err(RegistryError::AlreadyRegistered(name, registered.id));
+        }
+        let id = act.id();
+        self.actors.entry(id).or_default().insert(name.clone());
+        self.names.insert(
+            name,
+            Registered {
+                id,
+                act: Box::new(act),
+            },
+        );
+        Ok(())
+    }
+
+    pub(crate) fn unregister(&mut self, name: &str) -> Option<ActorId> {
+        let registered = self.names.remove(name)?;
+        if let Some(names) = self.actors.get_mut(&registered.id) {
+            names.remove(name);
+            if names.is_empty() {
+                self.actors.remove(&registered.id);
+            }
+        }
+        Some(registered.id)
+    }
+
+    pub(crate) fn unregister_actor(&mut self, id: ActorId) {
+        for name in self.actors.remove(&id).into_iter().flatten() {
+            self.names.remove(&name);
+        }
+    }
+
+    pub(crate) fn whereis<Msg: Send + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<Option<Act<Msg>>, RegistryError> {
+        let Some(registered) = self.names.get(name) else {
+            return Ok(None);
+        };
+        match registered.act.downcast_ref::<Act<Msg>>() {
+            Some(act) => Ok(Some(act.clone())),
+            None => Err(RegistryError::WrongType(name.to_string())),
+        }
+    }
+
+    pub(crate) fn registered(&self) -> Vec<(String, ActorId)> {
+        self.names
+            .iter()
+            .map(|(name, registered)| (name.clone(), registered.id))
+            .collect()
+    }
+}
@@ -1,13 +1,59 @@
-use anymap::{Map, any::Any};
-use slab::Slab;
-use tokio::sync::oneshot;
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
-use crate::{Reply, RpcEnvelope, RpcMessage};
+use tokio::{sync::oneshot, time::Instant};
 
-type SendAnyMap = Map<dyn Any + Send>;
+use crate::{RecvError, Reply, RpcEnvelope, RpcMessage};
 
+// Resolves to the response, or to the error the request was failed with by
+// `ReplyMap::sweep_expired` or `ReplyMap::fail_all`
+pub struct PendingReply<T> {
+    rx: oneshot::Receiver<T>,
+    error: oneshot::Receiver<RecvError>,
+}
+
+impl<T> Future for PendingReply<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.rx).poll(cx) {
+            Poll::Ready(Ok(value)) => Poll::Ready(Ok(value)),
+            // The error is always sent before the reply is dropped
+            Poll::Ready(Err(_)) => {
+                Poll::Ready(Err(self.error.try_recv().unwrap_or(RecvError::Closed)))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+struct Pending {
+    // Type-erased `Reply<T>`
+    reply: Box<dyn Any + Send>,
+    deadline: Option<Instant>,
+    error: Option<oneshot::Sender<RecvError>>,
+}
+
+// Set up by `create_request` for the reply that `RpcMessage::into_request` inserts
+struct NextRequest {
+    deadline: Option<Instant>,
+    error: Option<oneshot::Sender<RecvError>>,
+}
+
+// Pending requests share a single id space regardless of their response type, so an id
+// identifies one request for logging, cancellation and matching up responses. Ids start at 1;
+// requests that don't expect a response are sent with id 0.
+#[derive(Default)]
 pub struct ReplyMap {
-    reply_slabs: SendAnyMap,
+    next_id: u64,
+    pending: HashMap<u64, Pending>,
+    next_request: Option<NextRequest>,
 }
 
 impl ReplyMap {
@@ -15,20 +61,83 @@ impl ReplyMap {
         Self::default()
     }
 
-    pub fn create_request<F, M, T>(
+    pub fn create_request<F, M, T>(&mut self, f: F) -> (PendingReply<T>, RpcEnvelope<M::Request>)
+    where
+        F: FnOnce(Reply<T>) -> M,
+        M: RpcMessage,
+        T: Send + 'static,
+    {
+        self.create_request_with(None, f)
+    }
+
+    pub fn create_request_timeout<F, M, T>(
+        &mut self,
+        timeout: Duration,
+        f: F,
+    ) -> (PendingReply<T>, RpcEnvelope<M::Request>)
+    where
+        F: FnOnce(Reply<T>) -> M,
+        M: RpcMessage,
+        T: Send + 'static,
+    {
+        self.create_request_with(Some(Instant::now() + timeout), f)
+    }
+
+    pub fn create_request_deadline<F, M, T>(
+        &mut self,
+        deadline: Instant,
+        f: F,
+    ) -> (PendingReply<T>, RpcEnvelope<M::Request>)
+    where
+        F: FnOnce(Reply<T>) -> M,
+        M: RpcMessage,
+        T: Send + 'static,
+    {
+        self.create_request_with(Some(deadline), f)
+    }
+
+    fn create_request_with<F, M, T>(
         &mut self,
+        deadline: Option<Instant>,
         f: F,
-    ) -> (oneshot::Receiver<T>, RpcEnvelope<M::Request>)
+    ) -> (PendingReply<T>, RpcEnvelope<M::Request>)
     where
         F: FnOnce(Reply<T>) -> M,
         M: RpcMessage,
         T: Send + 'static,
     {
         let (tx, rx) = oneshot::channel();
+        let (error_tx, error_rx) = oneshot::channel();
+        self.next_request = Some(NextRequest {
+            deadline,
+            error: Some(error_tx),
+        });
         let reply = Reply::new(tx);
         let msg = f(reply);
         let env = msg.into_request(self);
-        (rx, env)
+        self.next_request = None;
+        let pending = PendingReply {
+            rx,
+            error: error_rx,
+        };
+        (pending, env)
+    }
+
+    // Like `create_request`, for a message whose `Reply` the caller created and is waiting on
+    // itself. Sweeping or failing the request drops the reply, which the caller sees as
+    // `RecvError::Closed`.
+    pub fn insert_request<M: RpcMessage>(
+        &mut self,
+        msg: M,
+        deadline: Option<Instant>,
+    ) -> RpcEnvelope<M::Request> {
+        self.next_request = Some(NextRequest {
+            deadline,
+            error: None,
+        });
+        let env = msg.into_request(self);
+        self.next_request = None;
+        env
     }
 
     pub async fn handle_response<M>(&mut self, env: RpcEnvelope<M::Response>) -> Result<(), ()>
@@ -38,24 +147,91 @@ impl ReplyMap {
         M::proxy_response(env, self).await
     }
 
-    pub fn get_reply<T: Send + 'static>(&mut self, id: usize) -> Option<Reply<T>> {
-        let slab = self.reply_slabs.get_mut::<Slab<Reply<T>>>()?;
-        slab.try_remove(id)
+    // Returns `None` if there is no pending request with this id, or if it expects a different
+    // response type, in which case it is left pending
+    pub fn get_reply<T: Send + 'static>(&mut self, id: u64) -> Option<Reply<T>> {
+        if !self.pending.get(&id)?.reply.is::<Reply<T>>() {
+            return None;
+        }
+        let pending = self.pending.remove(&id)?;
+        pending
+            .reply
+            .downcast::<Reply<T>>()
+            .ok()
+            .map(|reply| *reply)
     }
 
-    pub fn insert_reply<T: Send + 'static>(&mut self, reply: Reply<T>) -> usize {
-        let slab = match self.reply_slabs.entry::<Slab<Reply<T>>>() {
-            anymap::Entry::Occupied(entry) => entry.into_mut(),
-            anymap::Entry::Vacant(entry) => entry.insert(Slab::new()),
-        };
-        slab.insert(reply)
+    pub fn insert_reply<T: Send + 'static>(&mut self, reply: Reply<T>) -> u64 {
+        self.next_id += 1;
+        let id = self.next_id;
+
+        let next = self.next_request.take();
+        self.pending.insert(
+            id,
+            Pending {
+                reply: Box::new(reply),
+                deadline: next.as_ref().and_then(|next| next.deadline),
+                error: next.and_then(|next| next.error),
+            },
+        );
+
+        id
+    }
+
+    // Drops a pending request without failing it, so its `PendingReply` resolves to
+    // `RecvError::Closed`
+    pub fn cancel(&mut self, id: u64) -> bool {
+        self.pending.remove(&id).is_some()
     }
-}
 
-impl Default for ReplyMap {
-    fn default() -> Self {
-        Self {
-            reply_slabs: SendAnyMap::new(),
+    pub fn is_pending(&self, id: u64) -> bool {
+        self.pending.contains_key(&id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    // The earliest deadline of any pending request, for scheduling the next `sweep_expired`
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().filter_map(|p| p.deadline).min()
+    }
+
+    // Fails every request whose deadline has passed with `RecvError::Timeout`, returning how many
+    // were failed. Late responses to them are ignored.
+    pub fn sweep_expired(&mut self, now: Instant) -> usize {
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, pending)| pending.deadline.is_some_and(|deadline| deadline <= now))
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+
+        for id in &expired {
+            if let Some(pending) = self.pending.remove(id) {
+                fail(pending, RecvError::Timeout);
+            }
+        }
+
+        expired.len()
+    }
+
+    // Fails every pending request, e.g. when the transport they were sent over is gone
+    pub fn fail_all(&mut self, reason: RecvError) {
+        for (_, pending) in std::mem::take(&mut self.pending) {
+            fail(pending, reason);
         }
     }
 }
+
+// The error has to be sent before the reply is dropped, see `PendingReply::poll`
+fn fail(pending: Pending, reason: RecvError) {
+    if let Some(error) = pending.error {
+        let _ = error.send(reason);
+    }
+    drop(pending.reply);
+}
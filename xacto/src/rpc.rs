@@ -0,0 +1,519 @@
+use std::{io, net::SocketAddr, time::Duration};
+
+#[cfg(unix)]
+use std::path::Path;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Serialize, de::DeserializeOwned};
+use tokio::{
+    io::{ReadHalf, WriteHalf},
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::{mpsc, oneshot},
+    task::JoinSet,
+    time::Instant,
+};
+use tokio_util::{
+    bytes::Bytes,
+    codec::{FramedRead, FramedWrite, LengthDelimitedCodec},
+};
+
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+use crate::{
+    Act, CallError, Codec, CodecKind, Listener, MemoryConnector, MemoryListener, RecvError, Reply,
+    ReplyMap, RpcEnvelope, RpcMessage, SendError, Transport, recv_reply,
+};
+
+// Frames are prefixed with their length as a big-endian u32. Anything longer than this is treated
+// as a protocol error and closes the connection.
+const MAX_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+// Requests queued by an `RpcClient` that haven't been written to the connection yet
+const CLIENT_QUEUE: usize = 64;
+
+// Encoded frames queued on either end of a connection that haven't been written yet
+const CONNECTION_QUEUE: usize = 64;
+
+// How long a server waits to accept again after failing to, doubling up to the max while
+// accepting keeps failing
+const ACCEPT_BACKOFF_MIN: Duration = Duration::from_millis(5);
+const ACCEPT_BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+type FrameReader<T> = FramedRead<ReadHalf<T>, LengthDelimitedCodec>;
+type FrameWriter<T> = FramedWrite<WriteHalf<T>, LengthDelimitedCodec>;
+
+fn framed<T: Transport>(conn: T) -> (FrameReader<T>, FrameWriter<T>) {
+    let frames = LengthDelimitedCodec::builder()
+        .max_frame_length(MAX_FRAME_LENGTH)
+        .new_codec();
+    let (reader, writer) = tokio::io::split(conn);
+    (
+        FramedRead::new(reader, frames.clone()),
+        FramedWrite::new(writer, frames),
+    )
+}
+
+async fn next_frame<T: Transport>(reader: &mut FrameReader<T>) -> io::Result<Vec<u8>> {
+    match reader.next().await {
+        Some(frame) => Ok(frame?.to_vec()),
+        None => Err(io::ErrorKind::UnexpectedEof.into()),
+    }
+}
+
+fn no_common_codec() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "no codec in common with the peer",
+    )
+}
+
+// Every connection starts with the client sending a frame of the ids of the codecs it accepts, in
+// order of preference. The server answers with the id of the first one it also accepts, or with
+// an empty frame and closes the connection if there is none.
+async fn accept_codec<T: Transport, C: Codec>(
+    reader: &mut FrameReader<T>,
+    writer: &mut FrameWriter<T>,
+    accepted: &[C],
+) -> io::Result<C> {
+    let offered = next_frame(reader).await?;
+    let codec = offered.into_iter().find_map(|id| find_codec(accepted, id));
+    match codec {
+        Some(codec) => {
+            writer.send(Bytes::from(vec![codec.id()])).await?;
+            Ok(codec)
+        }
+        None => {
+            writer.send(Bytes::new()).await?;
+            Err(no_common_codec())
+        }
+    }
+}
+
+async fn offer_codecs<T: Transport, C: Codec>(
+    reader: &mut FrameReader<T>,
+    writer: &mut FrameWriter<T>,
+    offered: &[C],
+) -> io::Result<C> {
+    let ids = offered.iter().map(|codec| codec.id()).collect::<Vec<_>>();
+    writer.send(Bytes::from(ids)).await?;
+    match next_frame(reader).await?[..] {
+        [id] => find_codec(offered, id).ok_or_else(no_common_codec),
+        _ => Err(no_common_codec()),
+    }
+}
+
+fn find_codec<C: Codec>(codecs: &[C], id: u8) -> Option<C> {
+    codecs.iter().copied().find(|codec| codec.id() == id)
+}
+
+// Servers answer every request that expects a response, with `None` if the actor dropped its
+// `Reply` without sending one
+type ResponseFrame<T> = RpcEnvelope<Option<T>>;
+
+pub struct RpcServer<L = TcpListener, C = CodecKind> {
+    listener: L,
+    codecs: Vec<C>,
+}
+
+impl RpcServer {
+    pub async fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self::new(listener))
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+}
+
+#[cfg(unix)]
+impl RpcServer<UnixListener> {
+    pub fn bind_unix(path: impl AsRef<Path>) -> io::Result<Self> {
+        let listener = UnixListener::bind(path)?;
+        Ok(Self::new(listener))
+    }
+}
+
+impl RpcServer<MemoryListener> {
+    // A server that clients connect to in-process with `RpcClient::connect_memory`
+    pub fn memory() -> (Self, MemoryConnector) {
+        let (listener, connector) = MemoryListener::new();
+        (Self::new(listener), connector)
+    }
+}
+
+impl<L: Listener> RpcServer<L> {
+    pub fn new(listener: L) -> Self {
+        Self {
+            listener,
+            codecs: CodecKind::ALL.to_vec(),
+        }
+    }
+}
+
+impl<L: Listener, C: Codec> RpcServer<L, C> {
+    // The codecs clients may pick from, every compiled in codec by default
+    pub fn codecs<D: Codec>(self, codecs: &[D]) -> RpcServer<L, D> {
+        RpcServer {
+            listener: self.listener,
+            codecs: codecs.to_vec(),
+        }
+    }
+
+    // Accepts connections and dispatches their requests to `act`. Failing to accept a connection,
+    // e.g. because the process is out of file descriptors, backs off before accepting again.
+    // Dropping the future closes every connection.
+    pub async fn serve<M>(mut self, act: Act<M>)
+    where
+        M: RpcMessage + Send + 'static,
+        M::Request: DeserializeOwned + Send + 'static,
+        M::Response: Serialize + Send + 'static,
+    {
+        let mut connections = JoinSet::new();
+        let mut backoff = ACCEPT_BACKOFF_MIN;
+        loop {
+            tokio::select! {
+                conn = self.listener.accept() => match conn {
+                    Ok(conn) => {
+                        backoff = ACCEPT_BACKOFF_MIN;
+                        connections.spawn(serve_connection(conn, act.clone(), self.codecs.clone()));
+                    }
+                    Err(_) => {
+                        tokio::time::sleep(backoff).await;
+                        backoff = (backoff * 2).min(ACCEPT_BACKOFF_MAX);
+                    }
+                },
+                Some(_) = connections.join_next() => {}
+            }
+        }
+    }
+}
+
+// Writes frames queued by the rest of the connection, so that reading never waits on the peer
+// reading what has been written
+async fn write_frames<T: Transport>(
+    mut writer: FrameWriter<T>,
+    mut frames: mpsc::Receiver<Bytes>,
+) -> io::Result<()> {
+    while let Some(frame) = frames.recv().await {
+        writer.send(frame).await?;
+    }
+    Ok(())
+}
+
+async fn serve_connection<M, C: Codec>(
+    conn: impl Transport,
+    act: Act<M>,
+    codecs: Vec<C>,
+) -> io::Result<()>
+where
+    M: RpcMessage + Send + 'static,
+    M::Request: DeserializeOwned + Send + 'static,
+    M::Response: Serialize + Send + 'static,
+{
+    let (mut reader, mut writer) = framed(conn);
+    let codec = accept_codec(&mut reader, &mut writer, &codecs).await?;
+
+    // The writer and every request in flight, which are all cancelled once the connection closes
+    let mut tasks = JoinSet::new();
+    let (frames_tx, frames_rx) = mpsc::channel(CONNECTION_QUEUE);
+    tasks.spawn(write_frames(writer, frames_rx));
+
+    loop {
+        tokio::select! {
+            frame = reader.next() => {
+                let Some(frame) = frame else {
+                    return Ok(());
+                };
+                let env: RpcEnvelope<M::Request> = codec.decode(&frame?)?;
+
+                // Each request is proxied in its own task so that calls are answered as they
+                // complete, but against a stand-in for `act`, so that the messages can be cast here
+                // one at a time in the order they were read. The task holds the only handle to the
+                // stand-in, so that a request that is never forwarded closes it.
+                let (proxy_tx, mut proxy_rx) = mpsc::channel(1);
+                let proxy = Act::new(act.id(), proxy_tx);
+                let id = env.id;
+                let frames = frames_tx.clone();
+                tasks.spawn(async move {
+                    let result = M::proxy_request(env, move |msg| Some((msg, proxy))).await;
+                    if id != 0 {
+                        let payload = result.ok().flatten().map(|env| env.payload);
+                        let env: ResponseFrame<M::Response> = RpcEnvelope { id, payload };
+                        // Fails only once the writer has stopped, which closes the connection
+                        let _ = frames.send(Bytes::from(codec.encode(&env)?)).await;
+                    }
+                    Ok::<_, io::Error>(())
+                });
+
+                let msg = proxy_rx.recv().await.and_then(|signal| signal.into_live_msg());
+                if let Some(msg) = msg
+                    && act.cast(msg).await.is_err()
+                {
+                    // The actor is gone, which the client sees as the connection closing
+                    return Ok(());
+                }
+            }
+            Some(result) = tasks.join_next() => {
+                result.map_err(io::Error::other)??;
+            }
+        }
+    }
+}
+
+struct Outgoing<M> {
+    msg: M,
+    deadline: Option<Instant>,
+}
+
+// A connection to an `RpcServer` that can be used like an `Act` to the actor it serves. Calls
+// are multiplexed over the connection, and once it closes every pending and future call fails.
+pub struct RpcClient<M, C = CodecKind> {
+    tx: mpsc::Sender<Outgoing<M>>,
+    codec: C,
+}
+
+impl<M, C: Codec> Clone for RpcClient<M, C> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            codec: self.codec,
+        }
+    }
+}
+
+impl<M> RpcClient<M>
+where
+    M: RpcMessage + Send + 'static,
+    M::Request: Serialize + Send,
+    M::Response: DeserializeOwned + Send,
+{
+    // Runs the client over an already connected transport, offering every compiled in codec
+    pub async fn new(conn: impl Transport) -> io::Result<Self> {
+        Self::with_codecs(conn, CodecKind::ALL).await
+    }
+
+    pub async fn connect(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let stream = TcpStream::connect(addr).await?;
+        stream.set_nodelay(true)?;
+        Self::new(stream).await
+    }
+
+    #[cfg(unix)]
+    pub async fn connect_unix(path: impl AsRef<Path>) -> io::Result<Self> {
+        let stream = UnixStream::connect(path).await?;
+        Self::new(stream).await
+    }
+
+    pub async fn connect_memory(connector: &MemoryConnector) -> io::Result<Self> {
+        let stream = connector.connect()?;
+        Self::new(stream).await
+    }
+}
+
+impl<M, C> RpcClient<M, C>
+where
+    M: RpcMessage + Send + 'static,
+    M::Request: Serialize + Send,
+    M::Response: DeserializeOwned + Send,
+    C: Codec,
+{
+    // Fails with `io::ErrorKind::Unsupported` if the server accepts none of `codecs`
+    pub async fn with_codecs(conn: impl Transport, codecs: &[C]) -> io::Result<Self> {
+        let (mut reader, mut writer) = framed(conn);
+        let codec = offer_codecs(&mut reader, &mut writer, codecs).await?;
+
+        let (tx, rx) = mpsc::channel(CLIENT_QUEUE);
+        tokio::spawn(async move {
+            let mut replies = ReplyMap::new();
+            let _ = run_client(reader, writer, codec, rx, &mut replies).await;
+            replies.fail_all(RecvError::Closed);
+        });
+        Ok(Self { tx, codec })
+    }
+
+    // The codec negotiated for the connection
+    pub fn codec(&self) -> C {
+        self.codec
+    }
+
+    // Whether the connection has closed
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    async fn send(&self, msg: M, deadline: Option<Instant>) -> Result<(), SendError<M>> {
+        self.tx
+            .send(Outgoing { msg, deadline })
+            .await
+            .map_err(|e| SendError::Closed(e.0.msg))
+    }
+
+    fn try_send(&self, msg: M, deadline: Option<Instant>) -> Result<(), SendError<M>> {
+        match self.tx.try_send(Outgoing { msg, deadline }) {
+            Ok(()) => Ok(()),
+            Err(mpsc::error::TrySendError::Full(e)) => Err(SendError::Full(e.msg)),
+            Err(mpsc::error::TrySendError::Closed(e)) => Err(SendError::Closed(e.msg)),
+        }
+    }
+
+    pub async fn cast(&self, msg: M) -> Result<(), SendError<M>> {
+        self.send(msg, None).await
+    }
+
+    pub fn try_cast(&self, msg: M) -> Result<(), SendError<M>> {
+        self.try_send(msg, None)
+    }
+
+    pub async fn call<T, F>(&self, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+
+        self.send(msg, None).await.map_err(CallError::Send)?;
+
+        recv_reply(rx, None).await.map_err(CallError::Recv)
+    }
+
+    pub async fn call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        self.call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn call_deadline<T, F>(&self, deadline: Instant, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+
+        match tokio::time::timeout_at(deadline, self.send(msg, Some(deadline))).await {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(CallError::Send(e)),
+            Err(_) => return Err(CallError::Recv(RecvError::Timeout)),
+        }
+
+        recv_reply_by(rx, deadline).await.map_err(CallError::Recv)
+    }
+
+    pub async fn try_call<T, F>(&self, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+
+        self.try_send(msg, None).map_err(CallError::Send)?;
+
+        recv_reply(rx, None).await.map_err(CallError::Recv)
+    }
+
+    pub async fn try_call_timeout<T, F>(&self, timeout: Duration, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        self.try_call_deadline(Instant::now() + timeout, f).await
+    }
+
+    pub async fn try_call_deadline<T, F>(&self, deadline: Instant, f: F) -> Result<T, CallError<M>>
+    where
+        T: Send + 'static,
+        F: FnOnce(Reply<T>) -> M,
+    {
+        let (tx, rx) = oneshot::channel();
+        let msg = f(Reply::new(tx));
+
+        self.try_send(msg, Some(deadline))
+            .map_err(CallError::Send)?;
+
+        recv_reply_by(rx, deadline).await.map_err(CallError::Recv)
+    }
+}
+
+// The connection drops a request once its deadline has passed, which can happen just before the
+// caller's own timeout fires
+async fn recv_reply_by<T>(rx: oneshot::Receiver<T>, deadline: Instant) -> Result<T, RecvError> {
+    match recv_reply(rx, Some(deadline)).await {
+        Err(RecvError::Closed) if Instant::now() >= deadline => Err(RecvError::Timeout),
+        result => result,
+    }
+}
+
+async fn run_client<M, T, C>(
+    mut reader: FrameReader<T>,
+    writer: FrameWriter<T>,
+    codec: C,
+    mut rx: mpsc::Receiver<Outgoing<M>>,
+    replies: &mut ReplyMap,
+) -> io::Result<()>
+where
+    M: RpcMessage + Send + 'static,
+    M::Request: Serialize + Send,
+    M::Response: DeserializeOwned + Send,
+    T: Transport,
+    C: Codec,
+{
+    // Cancelled if the connection closes before every request has been written
+    let mut writing = JoinSet::new();
+    let (frames_tx, frames_rx) = mpsc::channel(CONNECTION_QUEUE);
+    writing.spawn(write_frames(writer, frames_rx));
+
+    loop {
+        let next_deadline = replies.next_deadline();
+        tokio::select! {
+            // Only taken once the writer has room, so that requests are held back in `rx`
+            (permit, outgoing) = async { (frames_tx.reserve().await, rx.recv().await) } => {
+                // Every client has been dropped
+                let Some(Outgoing { msg, deadline }) = outgoing else {
+                    break;
+                };
+                let Ok(permit) = permit else {
+                    break;
+                };
+                let env = replies.insert_request(msg, deadline);
+                permit.send(Bytes::from(codec.encode(&env)?));
+            }
+            frame = reader.next() => {
+                let Some(frame) = frame else {
+                    return Ok(());
+                };
+                let env: ResponseFrame<M::Response> = codec.decode(&frame?)?;
+                // Responses to requests that have already timed out are ignored
+                match env.payload {
+                    Some(payload) => {
+                        let env = RpcEnvelope { id: env.id, payload };
+                        let _ = replies.handle_response::<M>(env).await;
+                    }
+                    None => {
+                        replies.cancel(env.id);
+                    }
+                }
+            }
+            _ = tokio::time::sleep_until(next_deadline.unwrap_or_else(Instant::now)),
+                if next_deadline.is_some() =>
+            {
+                replies.sweep_expired(Instant::now());
+            }
+            Some(result) = writing.join_next() => {
+                result.map_err(io::Error::other)??;
+            }
+        }
+    }
+
+    // Finish writing the casts queued before the last client was dropped
+    drop(frames_tx);
+    while let Some(result) = writing.join_next().await {
+        result.map_err(io::Error::other)??;
+    }
+    Ok(())
+}
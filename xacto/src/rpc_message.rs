@@ -5,7 +5,8 @@ use crate::{Act, ReplyMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcEnvelope<T> {
-    pub id: usize,
+    // Request id from `ReplyMap`, or 0 if no response is expected
+    pub id: u64,
     pub payload: T,
 }
 
@@ -1,21 +1,41 @@
 use std::{
-    collections::HashMap,
+    collections::{BinaryHeap, HashMap, HashSet},
     panic::AssertUnwindSafe,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
 use futures_util::FutureExt;
 use tokio::{
     sync::mpsc,
     task::{AbortHandle, JoinError, JoinSet},
+    time::Instant,
 };
 use tokio_util::sync::CancellationToken;
 
-use crate::{Act, Actor, ActorId, ActorSelf, ActorTask, ActorTaskError};
+use crate::{
+    Act, Actor, ActorError, ActorId, ActorPool, ActorResult, ActorSelf, ActorSpec, ActorTask,
+    ActorTaskError, Down, DownReason, ExitReason, ExitSignal, GroupMembers, MonitorRef, PoolRef,
+    RegistryError, SignalSender, Supervisor, SupervisorMsg, SupervisorSpec, groups::Groups,
+    mailbox, registry::Registry,
+};
 
 pub struct ScopeContext {
     next_scope_id: u32,
+    next_monitor_id: u64,
     cancel: CancellationToken,
+    actors: HashMap<ActorId, ActorEntry>,
+    // monitor -> (watcher, target)
+    monitors: HashMap<MonitorRef, (ActorId, ActorId)>,
+    registry: Registry,
+    groups: Groups,
+}
+
+struct ActorEntry {
+    signals: Box<dyn SignalSender>,
+    monitoring: HashSet<MonitorRef>,
+    monitored_by: HashSet<MonitorRef>,
+    links: HashSet<ActorId>,
 }
 
 impl ScopeContext {
@@ -23,25 +43,250 @@ impl ScopeContext {
         let cancel = CancellationToken::new();
         Arc::new(Mutex::new(Self {
             next_scope_id: 0,
+            next_monitor_id: 0,
             cancel,
+            actors: HashMap::new(),
+            monitors: HashMap::new(),
+            registry: Registry::default(),
+            groups: Groups::default(),
         }))
     }
+
+    pub fn is_alive(&self, id: ActorId) -> bool {
+        self.actors.contains_key(&id)
+    }
+
+    pub fn monitor(&mut self, watcher: ActorId, target: ActorId) -> MonitorRef {
+        let monitor = MonitorRef::new(self.next_monitor_id);
+        self.next_monitor_id += 1;
+
+        if !self.actors.contains_key(&watcher) {
+            return monitor;
+        }
+
+        if let Some(entry) = self.actors.get_mut(&target) {
+            entry.monitored_by.insert(monitor);
+            self.monitors.insert(monitor, (watcher, target));
+            if let Some(entry) = self.actors.get_mut(&watcher) {
+                entry.monitoring.insert(monitor);
+            }
+        } else if let Some(entry) = self.actors.get(&watcher) {
+            entry.signals.send_down(Down {
+                monitor,
+                id: target,
+                reason: DownReason::NoProc,
+            });
+        }
+
+        monitor
+    }
+
+    pub fn demonitor(&mut self, monitor: MonitorRef) {
+        if let Some((watcher, target)) = self.monitors.remove(&monitor) {
+            if let Some(entry) = self.actors.get_mut(&watcher) {
+                entry.monitoring.remove(&monitor);
+            }
+            if let Some(entry) = self.actors.get_mut(&target) {
+                entry.monitored_by.remove(&monitor);
+            }
+        }
+    }
+
+    pub fn link(&mut self, a: ActorId, b: ActorId) -> ActorResult {
+        if a == b {
+            return Ok(());
+        }
+        if !self.actors.contains_key(&a) {
+            return Err(ActorError::NoProc(a));
+        }
+        let Some(entry) = self.actors.get_mut(&b) else {
+            return Err(ActorError::NoProc(b));
+        };
+        entry.links.insert(a);
+        if let Some(entry) = self.actors.get_mut(&a) {
+            entry.links.insert(b);
+        }
+        Ok(())
+    }
+
+    pub fn unlink(&mut self, a: ActorId, b: ActorId) {
+        if let Some(entry) = self.actors.get_mut(&a) {
+            entry.links.remove(&b);
+        }
+        if let Some(entry) = self.actors.get_mut(&b) {
+            entry.links.remove(&a);
+        }
+    }
+
+    // Names are unregistered automatically when the actor finishes
+    pub fn register<Msg: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        if !self.actors.contains_key(&act.id()) {
+            return Err(RegistryError::NoProc(act.id()));
+        }
+        self.registry.register(name.into(), act)
+    }
+
+    pub fn unregister(&mut self, name: &str) -> Option<ActorId> {
+        self.registry.unregister(name)
+    }
+
+    // Fails with `RegistryError::WrongType` if `name` belongs to an actor with another message
+    // type
+    pub fn whereis<Msg: Send + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<Option<Act<Msg>>, RegistryError> {
+        self.registry.whereis(name)
+    }
+
+    pub fn registered(&self) -> Vec<(String, ActorId)> {
+        self.registry.registered()
+    }
+
+    // Every member of a group has the same message type. Actors leave their groups automatically
+    // when they finish.
+    pub fn join<Msg: Send + 'static>(
+        &mut self,
+        group: impl Into<String>,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        if !self.actors.contains_key(&act.id()) {
+            return Err(RegistryError::NoProc(act.id()));
+        }
+        self.groups.join(group.into(), act)
+    }
+
+    pub fn leave(&mut self, group: &str, id: ActorId) {
+        self.groups.leave(group, id);
+    }
+
+    pub fn members<Msg: Send + 'static>(
+        &mut self,
+        group: &str,
+    ) -> Result<GroupMembers<Msg>, RegistryError> {
+        self.groups.members(group)
+    }
+
+    fn register_actor(&mut self, id: ActorId, signals: Box<dyn SignalSender>) {
+        let entry = ActorEntry {
+            signals,
+            monitoring: HashSet::new(),
+            monitored_by: HashSet::new(),
+            links: HashSet::new(),
+        };
+        self.actors.insert(id, entry);
+    }
+
+    fn actor_finished(&mut self, id: ActorId, reason: DownReason) {
+        let Some(entry) = self.actors.remove(&id) else {
+            return;
+        };
+
+        self.registry.unregister_actor(id);
+        self.groups.leave_all(id);
+
+        for monitor in entry.monitoring {
+            self.demonitor(monitor);
+        }
+
+        for monitor in entry.monitored_by {
+            let Some((watcher, _)) = self.monitors.remove(&monitor) else {
+                continue;
+            };
+            if let Some(entry) = self.actors.get_mut(&watcher) {
+                entry.monitoring.remove(&monitor);
+                entry.signals.send_down(Down {
+                    monitor,
+                    id,
+                    reason: reason.clone(),
+                });
+            }
+        }
+
+        for peer in entry.links {
+            if let Some(entry) = self.actors.get_mut(&peer) {
+                entry.links.remove(&id);
+                entry.signals.send_exit(ExitSignal {
+                    from: id,
+                    reason: reason.clone(),
+                });
+            }
+        }
+    }
+}
+
+// Reports the actor as finished to the `ScopeContext` when its task completes or is dropped
+// without completing, which is what happens when it is aborted
+struct FinishGuard {
+    context: Arc<Mutex<ScopeContext>>,
+    id: ActorId,
+    reason: Option<DownReason>,
+    cancel: CancellationToken,
+    finished: CancellationToken,
+}
+
+impl FinishGuard {
+    fn finish(mut self, output: &ActorOutput) {
+        self.reason = Some(output.into());
+    }
+}
+
+impl Drop for FinishGuard {
+    fn drop(&mut self) {
+        let reason = self.reason.take().unwrap_or(DownReason::Aborted);
+        if let Ok(mut context) = self.context.lock() {
+            context.actor_finished(self.id, reason);
+        }
+        // Stops anything tied to the actor's token, like timers, however the actor finished
+        self.cancel.cancel();
+        self.finished.cancel();
+    }
 }
 
 struct ActorState {
     handle: AbortHandle,
     cancel: CancellationToken,
+    started: CancellationToken,
+    finished: CancellationToken,
 }
 
 #[derive(Debug)]
 pub enum ActorOutput {
-    Success,
+    Exited(ExitReason),
     Failed(ActorTaskError),
     Aborted,
     Panicked(Option<Box<dyn std::any::Any + Send>>),
     Unknown(JoinError),
 }
 
+// Which actors exited on their own, failed, or had to be aborted during a shutdown
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    pub exited: Vec<ActorId>,
+    pub failed: Vec<ActorId>,
+    pub aborted: Vec<ActorId>,
+}
+
+impl ShutdownReport {
+    pub fn is_clean(&self) -> bool {
+        self.failed.is_empty() && self.aborted.is_empty()
+    }
+
+    fn record(&mut self, id: ActorId, output: ActorOutput) {
+        match output {
+            ActorOutput::Exited(_) => self.exited.push(id),
+            ActorOutput::Aborted => self.aborted.push(id),
+            ActorOutput::Failed(_) | ActorOutput::Panicked(_) | ActorOutput::Unknown(_) => {
+                self.failed.push(id)
+            }
+        }
+    }
+}
+
 pub struct Scope {
     context: Arc<Mutex<ScopeContext>>,
     id: u32,
@@ -49,6 +294,8 @@ pub struct Scope {
     join_set: JoinSet<ActorOutput>,
     task_ids: HashMap<tokio::task::Id, ActorId>,
     actors: HashMap<ActorId, ActorState>,
+    // dependent -> dependencies
+    dependencies: HashMap<ActorId, HashSet<ActorId>>,
     cancel: CancellationToken,
 }
 
@@ -69,10 +316,20 @@ impl Scope {
             join_set: JoinSet::new(),
             task_ids: HashMap::new(),
             actors: HashMap::new(),
+            dependencies: HashMap::new(),
             cancel,
         }
     }
 
+    pub async fn start_tree(&mut self, spec: SupervisorSpec) -> Act<SupervisorMsg> {
+        Supervisor::spawn(self, spec).await
+    }
+
+    // Starts the pool's workers and waits until they have all started
+    pub async fn start_pool<A: Actor>(&mut self, pool: ActorPool<A>) -> PoolRef<A::Msg> {
+        pool.start(self).await
+    }
+
     pub fn id(&self) -> u32 {
         self.id
     }
@@ -92,44 +349,234 @@ impl Scope {
             join_set: JoinSet::new(),
             task_ids: HashMap::new(),
             actors: HashMap::new(),
+            dependencies: HashMap::new(),
             cancel,
         }
     }
 
     pub async fn spawn<A: Actor>(&mut self, args: A::Args) -> Act<A::Msg> {
+        self.spawn_with::<A>(ActorSpec::default(), args).await
+    }
+
+    pub async fn spawn_with<A: Actor>(&mut self, spec: ActorSpec, args: A::Args) -> Act<A::Msg> {
+        self.__spawn::<A>(spec, args, None).0
+    }
+
+    // `watcher` monitors the actor from before it starts running, so it is sent a `Down` with the
+    // real reason even if the actor fails in `start`
+    pub async fn spawn_monitored<A: Actor>(
+        &mut self,
+        watcher: ActorId,
+        spec: ActorSpec,
+        args: A::Args,
+    ) -> (Act<A::Msg>, MonitorRef) {
+        let (act, monitor) = self.__spawn::<A>(spec, args, Some(watcher));
+        (act, monitor.expect("monitor not registered"))
+    }
+
+    fn __spawn<A: Actor>(
+        &mut self,
+        spec: ActorSpec,
+        args: A::Args,
+        watcher: Option<ActorId>,
+    ) -> (Act<A::Msg>, Option<MonitorRef>) {
         assert!(!self.cancel.is_cancelled(), "scope cancelled");
 
         let id = ActorId(self.id, self.next_actor_id);
         self.next_actor_id += 1;
 
-        let (tx, rx) = mpsc::channel(100);
-        let act = Act::new(id, tx.clone());
+        let (tx, rx) = mailbox::channel(&spec);
+        let (signals_tx, signals_rx) = mpsc::unbounded_channel();
+        let act = Act::from_mailbox(id, tx, Arc::downgrade(&rx)).with_overflow(spec.overflow);
         let cancel = self.cancel.child_token();
 
-        let this = ActorSelf::new(act.clone(), rx, cancel.clone());
+        let monitor = {
+            let mut context = self.context.lock().unwrap();
+            context.register_actor(id, Box::new(signals_tx.clone()));
+            watcher.map(|watcher| context.monitor(watcher, id))
+        };
+
+        let this = ActorSelf::new(
+            act.clone(),
+            rx,
+            signals_tx,
+            signals_rx,
+            cancel.clone(),
+            self.context.clone(),
+        )
+        .with_spec(spec);
         let task = ActorTask::<A>::new(this);
+        let started = task.started();
         let task = task.run(args);
 
+        let finished = CancellationToken::new();
+        let guard = FinishGuard {
+            context: self.context.clone(),
+            id,
+            reason: None,
+            cancel: cancel.clone(),
+            finished: finished.clone(),
+        };
+
         let handle = self.join_set.spawn(async move {
-            match AssertUnwindSafe(task).catch_unwind().await {
+            // Declared before the task so that an aborted task drops the actor before the guard
+            // reports it as finished
+            let guard = guard;
+            let task = AssertUnwindSafe(task).catch_unwind();
+            let output = match task.await {
                 Ok(result) => match result {
-                    Ok(()) => ActorOutput::Success,
+                    Ok(reason) => ActorOutput::Exited(reason),
                     Err(e) => ActorOutput::Failed(e),
                 },
                 Err(e) => ActorOutput::Panicked(Some(e)),
-            }
+            };
+            guard.finish(&output);
+            output
         });
 
         self.task_ids.insert(handle.id(), id);
-        self.actors.insert(id, ActorState { handle, cancel });
+        self.actors.insert(
+            id,
+            ActorState {
+                handle,
+                cancel,
+                started,
+                finished,
+            },
+        );
+
+        (act, monitor)
+    }
+
+    pub fn monitor(&mut self, watcher: ActorId, target: ActorId) -> MonitorRef {
+        self.context.lock().unwrap().monitor(watcher, target)
+    }
+
+    pub fn demonitor(&mut self, monitor: MonitorRef) {
+        self.context.lock().unwrap().demonitor(monitor);
+    }
+
+    pub fn link(&mut self, a: ActorId, b: ActorId) -> ActorResult {
+        self.context.lock().unwrap().link(a, b)
+    }
+
+    pub fn unlink(&mut self, a: ActorId, b: ActorId) {
+        self.context.lock().unwrap().unlink(a, b);
+    }
+
+    // On shutdown, `dependent` is stopped and its `exit` completes before `dependency` is asked to
+    // exit
+    pub fn depends_on(&mut self, dependent: ActorId, dependency: ActorId) {
+        self.dependencies
+            .entry(dependent)
+            .or_default()
+            .insert(dependency);
+    }
+
+    // Dependents come before their dependencies, and otherwise the most recently spawned actors
+    // come first. Actors in a dependency cycle are stopped in reverse spawn order.
+    fn shutdown_order(&self) -> Vec<ActorId> {
+        let mut dependents = self
+            .actors
+            .keys()
+            .map(|id| (*id, 0))
+            .collect::<HashMap<_, _>>();
+        for dependency in self.running_dependencies() {
+            *dependents.get_mut(&dependency).unwrap() += 1;
+        }
+
+        let mut ready = dependents
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(id, _)| *id)
+            .collect::<BinaryHeap<_>>();
+        let mut order = Vec::with_capacity(dependents.len());
+
+        while order.len() < dependents.len() {
+            let id = match ready.pop() {
+                Some(id) => id,
+                None => dependents
+                    .iter()
+                    .filter(|(_, count)| **count > 0)
+                    .map(|(id, _)| *id)
+                    .max()
+                    .unwrap(),
+            };
+            dependents.insert(id, 0);
+            order.push(id);
+
+            for dependency in self.dependencies.get(&id).into_iter().flatten() {
+                if let Some(count) = dependents.get_mut(dependency)
+                    && *count > 0
+                {
+                    *count -= 1;
+                    if *count == 0 {
+                        ready.push(*dependency);
+                    }
+                }
+            }
+        }
+
+        order
+    }
+
+    fn running_dependencies(&self) -> impl Iterator<Item = ActorId> + '_ {
+        self.dependencies
+            .iter()
+            .filter(|(dependent, _)| self.actors.contains_key(dependent))
+            .flat_map(|(_, dependencies)| dependencies)
+            .filter(|dependency| self.actors.contains_key(dependency))
+            .copied()
+    }
+
+    pub fn register<Msg: Send + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        self.context.lock().unwrap().register(name, act)
+    }
 
-        act
+    pub fn whereis<Msg: Send + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<Option<Act<Msg>>, RegistryError> {
+        self.context.lock().unwrap().whereis(name)
+    }
+
+    pub fn join<Msg: Send + 'static>(
+        &mut self,
+        group: impl Into<String>,
+        act: Act<Msg>,
+    ) -> Result<(), RegistryError> {
+        self.context.lock().unwrap().join(group, act)
+    }
+
+    pub fn leave(&mut self, group: &str, id: ActorId) {
+        self.context.lock().unwrap().leave(group, id);
+    }
+
+    pub fn members<Msg: Send + 'static>(
+        &self,
+        group: &str,
+    ) -> Result<GroupMembers<Msg>, RegistryError> {
+        self.context.lock().unwrap().members(group)
     }
 
     pub fn is_running(&self, id: ActorId) -> bool {
         self.actors.contains_key(&id)
     }
 
+    // Waits until the actor has either started or finished
+    pub async fn wait_started(&self, id: ActorId) {
+        if let Some(state) = self.actors.get(&id) {
+            tokio::select! {
+                _ = state.started.cancelled() => {}
+                _ = state.finished.cancelled() => {}
+            }
+        }
+    }
+
     pub fn exit_actor(&mut self, id: ActorId) {
         if let Some(state) = self.actors.get_mut(&id) {
             state.cancel.cancel();
@@ -145,31 +592,61 @@ impl Scope {
     fn __cleanup_actor_state(&mut self, task_id: tokio::task::Id) -> (ActorId, ActorState) {
         let id = self.task_ids.remove(&task_id).expect("task id not found");
         let state = self.actors.remove(&id).expect("actor not found");
+        self.dependencies.remove(&id);
         (id, state)
     }
 
+    pub async fn exit_actor_and_wait(&mut self, id: ActorId) {
+        if let Some(state) = self.actors.get(&id) {
+            state.cancel.cancel();
+            state.finished.cancelled().await;
+        }
+    }
+
+    // Aborts the actor if it hasn't finished exiting within `timeout`
+    pub async fn exit_actor_and_wait_timeout(&mut self, id: ActorId, timeout: Duration) {
+        if let Some(state) = self.actors.get(&id) {
+            state.cancel.cancel();
+            if tokio::time::timeout(timeout, state.finished.cancelled())
+                .await
+                .is_err()
+            {
+                state.handle.abort();
+                state.finished.cancelled().await;
+            }
+        }
+    }
+
     pub async fn next_finished(&mut self) -> Option<(ActorId, ActorOutput)> {
-        if let Some(result) = self.join_set.join_next_with_id().await {
-            let output = match result {
-                Ok((task_id, output)) => {
-                    let (id, _) = self.__cleanup_actor_state(task_id);
-                    (id, output)
-                }
-                Err(e) => {
-                    let (id, _) = self.__cleanup_actor_state(e.id());
-
-                    if e.is_cancelled() {
-                        (id, ActorOutput::Aborted)
-                    } else if e.is_panic() {
-                        (id, ActorOutput::Panicked(None))
-                    } else {
-                        (id, ActorOutput::Unknown(e))
-                    }
+        let result = self.join_set.join_next_with_id().await?;
+        Some(self.__finished_output(result))
+    }
+
+    pub fn try_next_finished(&mut self) -> Option<(ActorId, ActorOutput)> {
+        let result = self.join_set.try_join_next_with_id()?;
+        Some(self.__finished_output(result))
+    }
+
+    fn __finished_output(
+        &mut self,
+        result: Result<(tokio::task::Id, ActorOutput), JoinError>,
+    ) -> (ActorId, ActorOutput) {
+        match result {
+            Ok((task_id, output)) => {
+                let (id, _) = self.__cleanup_actor_state(task_id);
+                (id, output)
+            }
+            Err(e) => {
+                let (id, _) = self.__cleanup_actor_state(e.id());
+
+                if e.is_cancelled() {
+                    (id, ActorOutput::Aborted)
+                } else if e.is_panic() {
+                    (id, ActorOutput::Panicked(None))
+                } else {
+                    (id, ActorOutput::Unknown(e))
                 }
-            };
-            Some(output)
-        } else {
-            None
+            }
         }
     }
 
@@ -177,11 +654,54 @@ impl Scope {
         self.cancel.cancel();
     }
 
-    pub async fn exit_and_wait(&mut self) {
+    // Stops the actors one at a time, in the order given by `depends_on` and then in reverse
+    // spawn order, waiting for each to finish before asking the next to exit
+    pub async fn exit_in_order(&mut self) {
+        for id in self.shutdown_order() {
+            self.exit_actor_and_wait(id).await;
+        }
         self.cancel.cancel();
+    }
+
+    pub async fn exit_and_wait(&mut self) {
+        self.exit_in_order().await;
         while self.next_finished().await.is_some() {}
     }
 
+    // Stops the actors in order like `exit_and_wait`, giving each an equal share of what is left
+    // of `timeout` before asking the next one to exit regardless. Actors that still haven't
+    // finished once `timeout` has elapsed are aborted.
+    pub async fn exit_and_wait_timeout(&mut self, timeout: Duration) -> ShutdownReport {
+        let deadline = Instant::now() + timeout;
+        let order = self.shutdown_order();
+        let mut left = order.len() as u32;
+        for id in order {
+            let share = deadline.saturating_duration_since(Instant::now()) / left;
+            left -= 1;
+            if let Some(state) = self.actors.get(&id) {
+                state.cancel.cancel();
+                let _ = tokio::time::timeout(share, state.finished.cancelled()).await;
+            }
+        }
+        self.cancel.cancel();
+
+        let mut report = ShutdownReport::default();
+        loop {
+            match tokio::time::timeout_at(deadline, self.next_finished()).await {
+                Ok(Some((id, output))) => report.record(id, output),
+                Ok(None) => break,
+                Err(_) => {
+                    self.abort_all();
+                    while let Some((id, output)) = self.next_finished().await {
+                        report.record(id, output);
+                    }
+                    break;
+                }
+            }
+        }
+        report
+    }
+
     pub fn abort_all(&mut self) {
         self.join_set.abort_all();
     }
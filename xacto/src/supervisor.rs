@@ -0,0 +1,453 @@
+use std::{
+    any::Any,
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use futures_util::future::BoxFuture;
+use tokio::time::Instant;
+
+use crate::{
+    Act, Actor, ActorError, ActorId, ActorResult, ActorSelf, ActorSpec, Down, ExitReason,
+    LinkPublisher, Reply, Scope,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartStrategy {
+    // Restart only the child that terminated
+    OneForOne,
+    // Restart every child when any one of them terminates
+    OneForAll,
+    // Restart the child that terminated and every child started after it
+    RestForOne,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Restart {
+    // Always restarted
+    Permanent,
+    // Restarted only if it went down abnormally, see `DownReason::is_abnormal`
+    Transient,
+    // Never restarted
+    Temporary,
+}
+
+// Children are started with a strong handle to them, which the supervisor holds on to so that
+// they keep running
+type ChildHandle = (ActorId, Box<dyn Any + Send>);
+
+// Children are monitored by `watcher` from before they start, so that failing to start is
+// reported like any other failure
+trait ChildStart: Send {
+    fn start<'a>(
+        &'a mut self,
+        scope: &'a mut Scope,
+        watcher: ActorId,
+    ) -> BoxFuture<'a, ChildHandle>;
+
+    fn stopped(&mut self) {}
+}
+
+pub struct WorkerSpec<A: Actor> {
+    name: String,
+    args: Box<dyn FnMut() -> A::Args + Send>,
+    restart: Restart,
+    shutdown: Option<Duration>,
+    spec: ActorSpec,
+    publisher: Option<LinkPublisher<A::Msg>>,
+}
+
+impl<A: Actor> WorkerSpec<A> {
+    // `args` is called again for every restart of the worker
+    pub fn new<F>(name: impl Into<String>, args: F) -> Self
+    where
+        F: FnMut() -> A::Args + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            args: Box::new(args),
+            restart: Restart::Permanent,
+            shutdown: Some(Duration::from_secs(5)),
+            spec: ActorSpec::default(),
+            publisher: None,
+        }
+    }
+
+    pub fn restart(mut self, restart: Restart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    // How long the worker gets to exit before it is aborted, or `None` to wait indefinitely
+    pub fn shutdown(mut self, shutdown: Option<Duration>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    pub fn mailbox(mut self, capacity: usize) -> Self {
+        self.spec = self.spec.bounded(capacity);
+        self
+    }
+
+    pub fn spec(mut self, spec: ActorSpec) -> Self {
+        self.spec = spec;
+        self
+    }
+
+    // Every incarnation of the worker is published through `publisher` once it has started, and
+    // cleared while the worker is down
+    pub fn publish(mut self, publisher: LinkPublisher<A::Msg>) -> Self {
+        self.publisher = Some(publisher);
+        self
+    }
+}
+
+struct WorkerStart<A: Actor> {
+    args: Box<dyn FnMut() -> A::Args + Send>,
+    spec: ActorSpec,
+    publisher: Option<LinkPublisher<A::Msg>>,
+}
+
+impl<A: Actor> ChildStart for WorkerStart<A> {
+    fn start<'a>(
+        &'a mut self,
+        scope: &'a mut Scope,
+        watcher: ActorId,
+    ) -> BoxFuture<'a, ChildHandle> {
+        let args = (self.args)();
+        Box::pin(async move {
+            let (act, _) = scope.spawn_monitored::<A>(watcher, self.spec, args).await;
+            scope.wait_started(act.id()).await;
+            if let Some(publisher) = &self.publisher {
+                publisher.update(act.clone());
+            }
+            (act.id(), Box::new(act) as Box<dyn Any + Send>)
+        })
+    }
+
+    fn stopped(&mut self) {
+        if let Some(publisher) = &self.publisher {
+            publisher.clear();
+        }
+    }
+}
+
+// The running supervisor takes the spec and hands it back when it is dropped, so that the same
+// subtree can be started again on restart
+struct SupervisorStart {
+    spec: Arc<Mutex<Option<SupervisorSpec>>>,
+}
+
+impl ChildStart for SupervisorStart {
+    fn start<'a>(
+        &'a mut self,
+        scope: &'a mut Scope,
+        watcher: ActorId,
+    ) -> BoxFuture<'a, ChildHandle> {
+        let mut spec = self
+            .spec
+            .lock()
+            .unwrap()
+            .take()
+            .expect("supervisor spec still in use");
+        spec.home = Some(self.spec.clone());
+        Box::pin(async move {
+            let (act, _) = scope
+                .spawn_monitored::<Supervisor>(watcher, ActorSpec::default(), spec)
+                .await;
+            scope.wait_started(act.id()).await;
+            (act.id(), Box::new(act) as Box<dyn Any + Send>)
+        })
+    }
+}
+
+pub struct ChildSpec {
+    name: String,
+    restart: Restart,
+    shutdown: Option<Duration>,
+    start: Box<dyn ChildStart>,
+}
+
+impl ChildSpec {
+    pub fn supervisor(name: impl Into<String>, spec: SupervisorSpec) -> Self {
+        Self {
+            name: name.into(),
+            restart: Restart::Permanent,
+            shutdown: None,
+            start: Box::new(SupervisorStart {
+                spec: Arc::new(Mutex::new(Some(spec))),
+            }),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn restart(mut self, restart: Restart) -> Self {
+        self.restart = restart;
+        self
+    }
+
+    pub fn shutdown(mut self, shutdown: Option<Duration>) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+}
+
+impl<A: Actor> From<WorkerSpec<A>> for ChildSpec {
+    fn from(spec: WorkerSpec<A>) -> Self {
+        Self {
+            name: spec.name,
+            restart: spec.restart,
+            shutdown: spec.shutdown,
+            start: Box::new(WorkerStart::<A> {
+                args: spec.args,
+                spec: spec.spec,
+                publisher: spec.publisher,
+            }),
+        }
+    }
+}
+
+pub struct SupervisorSpec {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    period: Duration,
+    children: Vec<ChildSpec>,
+    home: Option<Arc<Mutex<Option<SupervisorSpec>>>>,
+}
+
+impl SupervisorSpec {
+    pub fn new(strategy: RestartStrategy) -> Self {
+        Self {
+            strategy,
+            max_restarts: 3,
+            period: Duration::from_secs(5),
+            children: vec![],
+            home: None,
+        }
+    }
+
+    // More than `max_restarts` restarts within `period` stops the supervisor and all of its
+    // children, failing it with `ActorError::MaxRestarts`
+    pub fn intensity(mut self, max_restarts: usize, period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.period = period;
+        self
+    }
+
+    pub fn child(mut self, child: impl Into<ChildSpec>) -> Self {
+        self.children.push(child.into());
+        self
+    }
+
+    pub fn supervisor(self, name: impl Into<String>, spec: SupervisorSpec) -> Self {
+        self.child(ChildSpec::supervisor(name, spec))
+    }
+}
+
+#[derive(Debug)]
+pub enum SupervisorMsg {
+    WhichChildren(Reply<Vec<(String, Option<ActorId>)>>),
+}
+
+struct Child {
+    spec: ChildSpec,
+    id: Option<ActorId>,
+    act: Option<Box<dyn Any + Send>>,
+}
+
+pub struct Supervisor {
+    strategy: RestartStrategy,
+    max_restarts: usize,
+    period: Duration,
+    restarts: VecDeque<Instant>,
+    children: Vec<Child>,
+    home: Option<Arc<Mutex<Option<SupervisorSpec>>>>,
+    scope: Scope,
+}
+
+impl Supervisor {
+    // Spawns a supervisor and waits until its whole subtree has started
+    pub(crate) async fn spawn(scope: &mut Scope, spec: SupervisorSpec) -> Act<SupervisorMsg> {
+        let act = scope.spawn::<Supervisor>(spec).await;
+        scope.wait_started(act.id()).await;
+        act
+    }
+
+    async fn start_child(&mut self, this: &ActorSelf<Self>, index: usize) {
+        let child = &mut self.children[index];
+        let (id, act) = child.spec.start.start(&mut self.scope, this.id()).await;
+        child.id = Some(id);
+        child.act = Some(act);
+    }
+
+    async fn stop_child(&mut self, index: usize) {
+        let child = &mut self.children[index];
+        if let Some(id) = child.id.take() {
+            // Held until the child has stopped, so that it exits with `ExitReason::Shutdown`
+            // rather than noticing its mailbox was closed
+            let _act = child.act.take();
+            child.spec.start.stopped();
+            match child.spec.shutdown {
+                Some(timeout) => self.scope.exit_actor_and_wait_timeout(id, timeout).await,
+                None => self.scope.exit_actor_and_wait(id).await,
+            }
+        }
+    }
+
+    fn reap(&mut self) {
+        while self.scope.try_next_finished().is_some() {}
+    }
+
+    // Returns false if the restart exceeds the restart intensity
+    fn record_restart(&mut self) -> bool {
+        let now = Instant::now();
+        while let Some(at) = self.restarts.front() {
+            if now.duration_since(*at) > self.period {
+                self.restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+        self.restarts.push_back(now);
+        self.restarts.len() <= self.max_restarts
+    }
+
+    async fn restart(&mut self, this: &ActorSelf<Self>, index: usize) {
+        let range = match self.strategy {
+            RestartStrategy::OneForOne => index..index + 1,
+            RestartStrategy::OneForAll => 0..self.children.len(),
+            RestartStrategy::RestForOne => index..self.children.len(),
+        };
+
+        let mut restart = vec![];
+        for i in range.rev() {
+            if i == index {
+                restart.push(i);
+            } else if self.children[i].id.is_some() {
+                self.stop_child(i).await;
+                if self.children[i].spec.restart != Restart::Temporary {
+                    restart.push(i);
+                }
+            }
+        }
+
+        for i in restart.into_iter().rev() {
+            self.start_child(this, i).await;
+        }
+    }
+
+    async fn stop_all(&mut self) {
+        for i in (0..self.children.len()).rev() {
+            self.stop_child(i).await;
+        }
+        self.reap();
+    }
+}
+
+#[async_trait]
+impl Actor for Supervisor {
+    type Args = SupervisorSpec;
+    type Msg = SupervisorMsg;
+
+    async fn start(this: &ActorSelf<Self>, spec: Self::Args) -> ActorResult<Self> {
+        let children = spec
+            .children
+            .into_iter()
+            .map(|spec| Child {
+                spec,
+                id: None,
+                act: None,
+            })
+            .collect();
+
+        let mut supervisor = Self {
+            strategy: spec.strategy,
+            max_restarts: spec.max_restarts,
+            period: spec.period,
+            restarts: VecDeque::new(),
+            children,
+            home: spec.home,
+            scope: Scope::new(this.context().clone()),
+        };
+
+        for i in 0..supervisor.children.len() {
+            supervisor.start_child(this, i).await;
+        }
+
+        Ok(supervisor)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            SupervisorMsg::WhichChildren(reply) => {
+                let children = self
+                    .children
+                    .iter()
+                    .map(|child| (child.spec.name.clone(), child.id))
+                    .collect();
+                reply.send(children)?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn handle_down(&mut self, this: &ActorSelf<Self>, down: Down) -> ActorResult {
+        // Downs from children that were already stopped or replaced are stale
+        let Some(index) = self.children.iter().position(|c| c.id == Some(down.id)) else {
+            return Ok(());
+        };
+
+        let child = &mut self.children[index];
+        child.id = None;
+        child.act = None;
+        child.spec.start.stopped();
+        self.reap();
+
+        let restart = match self.children[index].spec.restart {
+            Restart::Permanent => true,
+            Restart::Transient => down.reason.is_abnormal(),
+            Restart::Temporary => false,
+        };
+
+        if !restart {
+            return Ok(());
+        }
+
+        if !self.record_restart() {
+            self.stop_all().await;
+            return Err(ActorError::MaxRestarts);
+        }
+
+        self.restart(this, index).await;
+
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        self.stop_all().await;
+        Ok(())
+    }
+}
+
+impl Drop for Supervisor {
+    fn drop(&mut self) {
+        let Some(home) = self.home.take() else {
+            return;
+        };
+        let spec = SupervisorSpec {
+            strategy: self.strategy,
+            max_restarts: self.max_restarts,
+            period: self.period,
+            children: self.children.drain(..).map(|child| child.spec).collect(),
+            home: None,
+        };
+        if let Ok(mut home) = home.lock() {
+            *home = Some(spec);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use tokio::{sync::mpsc, time::MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
+
+use crate::ActorSignal;
+
+#[derive(Debug, Clone)]
+pub struct TimerRef {
+    id: u64,
+    cancel: CancellationToken,
+}
+
+impl TimerRef {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    // A timer that is cancelled after it fired but before the actor handled it is not delivered
+    pub fn cancel(&self) {
+        self.cancel.cancel();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+impl PartialEq for TimerRef {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TimerRef {}
+
+pub(crate) fn send_after<M: Send + 'static>(
+    id: u64,
+    cancel: CancellationToken,
+    tx: mpsc::UnboundedSender<ActorSignal<M>>,
+    delay: Duration,
+    msg: M,
+) -> TimerRef {
+    let timer = TimerRef { id, cancel };
+    let task_timer = timer.clone();
+
+    tokio::spawn(async move {
+        tokio::select! {
+            _ = task_timer.cancel.cancelled() => {}
+            _ = tokio::time::sleep(delay) => {
+                let _ = tx.send(ActorSignal::Timer(task_timer, msg));
+            }
+        }
+    });
+
+    timer
+}
+
+pub(crate) fn send_interval<M, F>(
+    id: u64,
+    cancel: CancellationToken,
+    tx: mpsc::UnboundedSender<ActorSignal<M>>,
+    period: Duration,
+    mut f: F,
+) -> TimerRef
+where
+    M: Send + 'static,
+    F: FnMut() -> M + Send + 'static,
+{
+    let timer = TimerRef { id, cancel };
+    let task_timer = timer.clone();
+
+    tokio::spawn(async move {
+        let start = tokio::time::Instant::now() + period;
+        let mut interval = tokio::time::interval_at(start, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                _ = task_timer.cancel.cancelled() => break,
+                _ = interval.tick() => {
+                    let signal = ActorSignal::Timer(task_timer.clone(), f());
+                    if tx.send(signal).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    timer
+}
@@ -0,0 +1,89 @@
+use std::{future::pending, io};
+
+use async_trait::async_trait;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, DuplexStream},
+    net::{TcpListener, TcpStream},
+    sync::mpsc,
+};
+
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+
+// Buffer size of each direction of an in-memory connection
+const MEMORY_BUFFER: usize = 64 * 1024;
+
+// A connected byte stream that RPC frames are sent over
+pub trait Transport: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+impl<T> Transport for T where T: AsyncRead + AsyncWrite + Send + Unpin + 'static {}
+
+// Accepts incoming connections for an `RpcServer`
+#[async_trait]
+pub trait Listener: Send + 'static {
+    type Conn: Transport;
+
+    async fn accept(&mut self) -> io::Result<Self::Conn>;
+}
+
+#[async_trait]
+impl Listener for TcpListener {
+    type Conn = TcpStream;
+
+    async fn accept(&mut self) -> io::Result<TcpStream> {
+        let (stream, _) = TcpListener::accept(self).await?;
+        stream.set_nodelay(true)?;
+        Ok(stream)
+    }
+}
+
+#[cfg(unix)]
+#[async_trait]
+impl Listener for UnixListener {
+    type Conn = UnixStream;
+
+    async fn accept(&mut self) -> io::Result<UnixStream> {
+        let (stream, _) = UnixListener::accept(self).await?;
+        Ok(stream)
+    }
+}
+
+// Accepts connections made in-process through its `MemoryConnector`s, without any sockets
+pub struct MemoryListener {
+    rx: mpsc::UnboundedReceiver<DuplexStream>,
+}
+
+impl MemoryListener {
+    pub fn new() -> (Self, MemoryConnector) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self { rx }, MemoryConnector { tx })
+    }
+}
+
+#[async_trait]
+impl Listener for MemoryListener {
+    type Conn = DuplexStream;
+
+    async fn accept(&mut self) -> io::Result<DuplexStream> {
+        match self.rx.recv().await {
+            Some(stream) => Ok(stream),
+            // No more connections can be made, but the ones already accepted are left running
+            None => pending().await,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct MemoryConnector {
+    tx: mpsc::UnboundedSender<DuplexStream>,
+}
+
+impl MemoryConnector {
+    pub fn connect(&self) -> io::Result<DuplexStream> {
+        let (client, server) = tokio::io::duplex(MEMORY_BUFFER);
+        self.tx
+            .send(server)
+            .map_err(|_| io::Error::from(io::ErrorKind::ConnectionRefused))?;
+        Ok(client)
+    }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, RegistryError, Reply, Scope,
+    ScopeContext,
+};
+
+#[derive(Debug)]
+enum Msg {
+    Name(Reply<&'static str>),
+}
+
+struct Member {
+    name: &'static str,
+}
+
+#[async_trait]
+impl Actor for Member {
+    type Args = &'static str;
+    type Msg = Msg;
+
+    async fn start(this: &ActorSelf<Self>, name: Self::Args) -> ActorResult<Self> {
+        this.join("members")?;
+        Ok(Self { name })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Name(reply) => Ok(reply.send(self.name)?),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn call_any_skips_stopped_members() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let mut acts = vec![];
+    for name in ["a", "b", "c"] {
+        let act = scope.spawn::<Member>(name).await;
+        scope.wait_started(act.id()).await;
+        acts.push(act);
+    }
+
+    let members = scope.members::<Msg>("members").unwrap();
+    scope.exit_actor_and_wait(acts[0].id()).await;
+    scope.exit_actor_and_wait(acts[1].id()).await;
+
+    assert_eq!(members.call_any(Msg::Name).await.unwrap(), "c");
+
+    scope.exit_actor_and_wait(acts[2].id()).await;
+    assert!(members.call_any(Msg::Name).await.is_err());
+
+    scope.exit_and_wait().await;
+}
+
+#[tokio::test]
+async fn call_any_on_empty_group_fails() {
+    let scope = Scope::new(ScopeContext::new());
+
+    let members = scope.members::<Msg>("members").unwrap();
+    assert!(matches!(
+        members.call_any(Msg::Name).await,
+        Err(ActorError::Registry(RegistryError::EmptyGroup(_)))
+    ));
+}
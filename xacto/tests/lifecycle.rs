@@ -0,0 +1,72 @@
+use std::cell::Cell;
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ErrorAction, ExitReason, Reply, Scope, ScopeContext,
+};
+
+// `Send` but not `Sync`
+#[derive(Debug)]
+struct Flaky(Cell<u32>);
+
+impl std::fmt::Display for Flaky {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "flaky after {} attempts", self.0.get())
+    }
+}
+
+impl std::error::Error for Flaky {}
+
+#[derive(Debug)]
+enum Msg {
+    Fail,
+    Incarnation(Reply<usize>),
+}
+
+struct Worker {
+    incarnation: usize,
+}
+
+#[async_trait]
+impl Actor for Worker {
+    type Args = usize;
+    type Msg = Msg;
+
+    async fn start(_: &ActorSelf<Self>, incarnation: Self::Args) -> ActorResult<Self> {
+        Ok(Self { incarnation })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Fail => Err(Flaky(Cell::new(3)).into()),
+            Msg::Incarnation(reply) => Ok(reply.send(self.incarnation)?),
+        }
+    }
+
+    async fn on_receive_error(&mut self, _: &ActorSelf<Self>, error: ActorError) -> ErrorAction {
+        match &error {
+            ActorError::Unknown(e) if e.is::<Flaky>() => ErrorAction::Restart(error),
+            _ => ErrorAction::Stop(error),
+        }
+    }
+
+    async fn pre_restart(&mut self, _: &ActorSelf<Self>, _: ActorError) -> ActorResult<usize> {
+        Ok(self.incarnation + 1)
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn non_sync_error_restarts_through_hooks() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let worker = scope.spawn::<Worker>(1).await;
+
+    worker.cast(Msg::Fail).await.unwrap();
+    worker.cast(Msg::Fail).await.unwrap();
+    assert_eq!(worker.call(Msg::Incarnation).await.unwrap(), 3);
+
+    scope.exit_and_wait().await;
+}
@@ -0,0 +1,46 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+use xacto::{CallError, LinkError, LinkPublisher, RecvError, Reply};
+
+#[allow(dead_code)]
+#[derive(Debug)]
+enum Msg {
+    Get(Reply<u32>),
+}
+
+#[tokio::test]
+async fn call_timeout_on_unpublished_link_times_out() {
+    let (_publisher, link) = LinkPublisher::<Msg>::new();
+
+    let started = Instant::now();
+    let result = tokio::time::timeout(
+        Duration::from_secs(1),
+        link.call_timeout(Duration::from_millis(50), Msg::Get),
+    )
+    .await
+    .expect("call_timeout did not return");
+
+    assert!(matches!(
+        result,
+        Err(LinkError::Call(CallError::Recv(RecvError::Timeout)))
+    ));
+    assert!(started.elapsed() >= Duration::from_millis(50));
+}
+
+#[tokio::test]
+async fn try_call_timeout_on_unpublished_link_times_out() {
+    let (_publisher, link) = LinkPublisher::<Msg>::new();
+
+    let result = tokio::time::timeout(
+        Duration::from_secs(1),
+        link.try_call_timeout(Duration::from_millis(50), Msg::Get),
+    )
+    .await
+    .expect("try_call_timeout did not return");
+
+    assert!(matches!(
+        result,
+        Err(LinkError::Call(CallError::Recv(RecvError::Timeout)))
+    ));
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+use xacto::{
+    Actor, ActorResult, ActorSelf, ActorSpec, ExitReason, Overflow, Reply, Scope, ScopeContext,
+    SendError,
+};
+
+struct Busy {
+    drained: Vec<u32>,
+    received: Vec<u32>,
+}
+
+#[derive(Debug)]
+enum BusyMsg {
+    // Blocks until the first notify, then drains the mailbox and notifies the second
+    Block(Arc<Notify>, Arc<Notify>),
+    Value(u32),
+    Get(Reply<(Vec<u32>, Vec<u32>)>),
+}
+
+#[async_trait]
+impl Actor for Busy {
+    type Args = ();
+    type Msg = BusyMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self {
+            drained: vec![],
+            received: vec![],
+        })
+    }
+
+    async fn receive(&mut self, this: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            BusyMsg::Block(resume, drained) => {
+                resume.notified().await;
+                for msg in this.drain() {
+                    if let BusyMsg::Value(n) = msg {
+                        self.drained.push(n);
+                    }
+                }
+                drained.notify_one();
+            }
+            BusyMsg::Value(n) => self.received.push(n),
+            BusyMsg::Get(reply) => {
+                reply.send((self.drained.clone(), self.received.clone()))?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn drop_oldest_mailbox_stays_within_capacity() {
+    const CAPACITY: usize = 4;
+    const SENT: u32 = 100_000;
+
+    let mut scope = Scope::new(ScopeContext::new());
+    let spec = ActorSpec::new()
+        .bounded(CAPACITY)
+        .overflow(Overflow::DropOldest);
+    let act = scope.spawn_with::<Busy>(spec, ()).await;
+    scope.wait_started(act.id()).await;
+
+    let resume = Arc::new(Notify::new());
+    let drained = Arc::new(Notify::new());
+    act.cast(BusyMsg::Block(resume.clone(), drained.clone()))
+        .await
+        .unwrap();
+    // Wait for the actor to take the block message off the mailbox
+    while act.capacity() < CAPACITY {
+        tokio::task::yield_now().await;
+    }
+
+    for n in 0..SENT {
+        act.cast(BusyMsg::Value(n)).await.unwrap();
+        assert!(CAPACITY - act.capacity() <= CAPACITY);
+    }
+    assert_eq!(act.capacity(), 0);
+
+    resume.notify_one();
+    drained.notified().await;
+    let (drained, received) = act.call(BusyMsg::Get).await.unwrap();
+    let expected = (SENT - CAPACITY as u32..SENT).collect::<Vec<_>>();
+    assert_eq!(drained, expected);
+    assert!(received.is_empty());
+
+    scope.exit_and_wait().await;
+}
+
+#[tokio::test]
+async fn unbounded_mailbox_accepts_everything() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let act = scope
+        .spawn_with::<Busy>(ActorSpec::new().unbounded(), ())
+        .await;
+    scope.wait_started(act.id()).await;
+
+    let resume = Arc::new(Notify::new());
+    let drained = Arc::new(Notify::new());
+    act.cast(BusyMsg::Block(resume.clone(), drained.clone()))
+        .await
+        .unwrap();
+    for n in 0..10_000 {
+        act.try_cast(BusyMsg::Value(n)).unwrap();
+    }
+    assert_eq!(act.capacity(), usize::MAX);
+
+    resume.notify_one();
+    drained.notified().await;
+    let (drained, _) = act.call(BusyMsg::Get).await.unwrap();
+    assert_eq!(drained.len(), 10_000);
+
+    scope.exit_and_wait().await;
+}
+
+#[tokio::test]
+async fn drop_oldest_with_only_reserved_capacity_is_full() {
+    const CAPACITY: usize = 2;
+
+    let mut scope = Scope::new(ScopeContext::new());
+    let spec = ActorSpec::new()
+        .bounded(CAPACITY)
+        .overflow(Overflow::DropOldest);
+    let act = scope.spawn_with::<Busy>(spec, ()).await;
+    scope.wait_started(act.id()).await;
+
+    // Nothing is queued, so there is nothing to evict
+    let tx = act.tx().unwrap();
+    let _permits = tx.try_reserve_many(CAPACITY).unwrap();
+
+    assert!(matches!(
+        act.try_cast(BusyMsg::Value(0)),
+        Err(SendError::Full(BusyMsg::Value(0)))
+    ));
+    assert!(matches!(
+        act.cast(BusyMsg::Value(1)).await,
+        Err(SendError::Full(BusyMsg::Value(1)))
+    ));
+}
@@ -0,0 +1,53 @@
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorPool, ActorResult, ActorSelf, ExitReason, Reply, Routing, Scope, ScopeContext,
+};
+
+#[derive(Debug)]
+enum Msg {
+    Index(Reply<usize>),
+}
+
+struct Worker {
+    index: usize,
+}
+
+#[async_trait]
+impl Actor for Worker {
+    type Args = usize;
+    type Msg = Msg;
+
+    async fn start(_: &ActorSelf<Self>, index: Self::Args) -> ActorResult<Self> {
+        Ok(Self { index })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Index(reply) => Ok(reply.send(self.index)?),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn random_routing_reaches_every_worker() {
+    const SIZE: usize = 4;
+
+    let mut scope = Scope::new(ScopeContext::new());
+    let pool = scope
+        .start_pool(ActorPool::<Worker>::new(SIZE, |index| index).routing(Routing::Random))
+        .await;
+
+    let mut calls = [0; SIZE];
+    for _ in 0..400 {
+        calls[pool.call(Msg::Index).await.unwrap()] += 1;
+    }
+
+    // Each worker expects 100 calls, so this only fails for a broken generator
+    assert!(calls.iter().all(|&n| n > 50), "{calls:?}");
+
+    scope.exit_and_wait().await;
+}
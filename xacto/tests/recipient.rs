@@ -0,0 +1,85 @@
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::sync::Notify;
+use xacto::{
+    Actor, ActorResult, ActorSelf, CallError, ExitReason, RecvError, Reply, Scope, ScopeContext,
+};
+
+#[derive(Debug)]
+enum Msg {
+    Block(Arc<Notify>),
+    Incr(Reply<()>),
+    Count(Reply<usize>),
+}
+
+struct Counter {
+    count: usize,
+}
+
+#[async_trait]
+impl Actor for Counter {
+    type Args = ();
+    type Msg = Msg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self { count: 0 })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Block(resume) => resume.notified().await,
+            Msg::Incr(reply) => {
+                self.count += 1;
+                reply.send(())?;
+            }
+            Msg::Count(reply) => reply.send(self.count)?,
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn expired_calls_are_skipped() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let counter = scope.spawn::<Counter>(()).await;
+    let recipient = counter.recipient::<Msg>();
+
+    let resume = Arc::new(Notify::new());
+    recipient.cast(Msg::Block(resume.clone())).await.unwrap();
+
+    let result = recipient
+        .call_timeout(Duration::from_millis(20), Msg::Incr)
+        .await;
+    assert!(matches!(result, Err(CallError::Recv(RecvError::Timeout))));
+    let result = recipient
+        .try_call_timeout(Duration::from_millis(20), Msg::Incr)
+        .await;
+    assert!(matches!(result, Err(CallError::Recv(RecvError::Timeout))));
+
+    resume.notify_one();
+    assert_eq!(recipient.call(Msg::Count).await.unwrap(), 0);
+
+    scope.exit_and_wait().await;
+}
+
+#[tokio::test]
+async fn calls_within_the_deadline_are_handled() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let counter = scope.spawn::<Counter>(()).await;
+    let recipient = counter.recipient::<Msg>();
+
+    let deadline = tokio::time::Instant::now() + Duration::from_secs(1);
+    recipient.call_deadline(deadline, Msg::Incr).await.unwrap();
+    recipient
+        .try_call_deadline(deadline, Msg::Incr)
+        .await
+        .unwrap();
+    assert_eq!(recipient.call(Msg::Count).await.unwrap(), 2);
+
+    scope.exit_and_wait().await;
+}
@@ -0,0 +1,248 @@
+use std::{
+    io,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use tokio::{io::DuplexStream, sync::mpsc, task::JoinSet};
+use xacto::{
+    Act, Actor, ActorResult, ActorSelf, Codec, CodecKind, ExitReason, Listener, Reply, ReplyMap,
+    RpcClient, RpcEnvelope, RpcMessage, RpcServer, Scope, ScopeContext,
+};
+use xacto_derive::RpcMessage;
+
+struct Echo;
+
+#[derive(Debug, RpcMessage)]
+enum EchoMsg {
+    Echo(Vec<u8>, Reply<Vec<u8>>),
+}
+
+#[async_trait]
+impl Actor for Echo {
+    type Args = ();
+    type Msg = EchoMsg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            EchoMsg::Echo(bytes, reply) => Ok(reply.send(bytes)?),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+// Responses written while requests are still being read must not stall the connection once
+// both directions have filled the transport's buffers
+#[tokio::test(flavor = "multi_thread")]
+async fn concurrent_large_calls_complete() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let echo = scope.spawn::<Echo>(()).await;
+
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.serve(echo));
+    let client = RpcClient::<EchoMsg>::connect_memory(&connector)
+        .await
+        .unwrap();
+
+    let mut calls = JoinSet::new();
+    for i in 0..64u8 {
+        let client = client.clone();
+        calls.spawn(async move {
+            let payload = vec![i; 256 * 1024];
+            let echoed = client
+                .call(|reply| EchoMsg::Echo(payload.clone(), reply))
+                .await
+                .unwrap();
+            assert_eq!(echoed, payload);
+        });
+    }
+
+    tokio::time::timeout(Duration::from_secs(60), async {
+        while let Some(result) = calls.join_next().await {
+            result.unwrap();
+        }
+    })
+    .await
+    .expect("calls did not complete");
+
+    server.abort();
+    scope.exit_and_wait().await;
+}
+
+// Whichever codec is compiled in, under an id of its own
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Custom;
+
+impl Codec for Custom {
+    fn id(&self) -> u8 {
+        200
+    }
+
+    fn encode<T: Serialize>(&self, value: &T) -> io::Result<Vec<u8>> {
+        CodecKind::ALL[0].encode(value)
+    }
+
+    fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> io::Result<T> {
+        CodecKind::ALL[0].decode(bytes)
+    }
+}
+
+#[tokio::test]
+async fn custom_codecs_are_negotiated() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let echo = scope.spawn::<Echo>(()).await;
+
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.codecs(&[Custom]).serve(echo));
+
+    let client = RpcClient::<EchoMsg, _>::with_codecs(connector.connect().unwrap(), &[Custom])
+        .await
+        .unwrap();
+    assert_eq!(client.codec(), Custom);
+    let echoed = client
+        .call(|reply| EchoMsg::Echo(vec![1, 2, 3], reply))
+        .await
+        .unwrap();
+    assert_eq!(echoed, [1, 2, 3]);
+
+    // The built-in codecs aren't accepted by this server
+    let result = RpcClient::<EchoMsg>::connect_memory(&connector).await;
+    assert_eq!(result.err().unwrap().kind(), io::ErrorKind::Unsupported);
+
+    server.abort();
+    scope.exit_and_wait().await;
+}
+
+#[derive(Debug)]
+enum Note {
+    Keep(u32),
+    Reject,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum NoteRequest {
+    Keep(u32),
+    Reject,
+}
+
+// Written by hand, so that a request can fail before it is forwarded to the actor
+#[async_trait]
+impl RpcMessage for Note {
+    type Request = NoteRequest;
+    type Response = ();
+
+    fn into_request(self, _: &mut ReplyMap) -> RpcEnvelope<NoteRequest> {
+        let payload = match self {
+            Note::Keep(n) => NoteRequest::Keep(n),
+            Note::Reject => NoteRequest::Reject,
+        };
+        RpcEnvelope { id: 0, payload }
+    }
+
+    async fn proxy_request<F: Send>(
+        env: RpcEnvelope<NoteRequest>,
+        f: F,
+    ) -> Result<Option<RpcEnvelope<()>>, ()>
+    where
+        F: FnOnce(Self) -> Option<(Self, Act<Self>)>,
+        Self: Sized,
+    {
+        let NoteRequest::Keep(n) = env.payload else {
+            return Err(());
+        };
+        let (msg, act) = f(Note::Keep(n)).ok_or(())?;
+        act.cast(msg).await.map_err(|_| ())?;
+        Ok(None)
+    }
+
+    async fn proxy_response(_: RpcEnvelope<()>, _: &mut ReplyMap) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+struct Notes {
+    seen: mpsc::UnboundedSender<u32>,
+}
+
+#[async_trait]
+impl Actor for Notes {
+    type Args = mpsc::UnboundedSender<u32>;
+    type Msg = Note;
+
+    async fn start(_: &ActorSelf<Self>, seen: Self::Args) -> ActorResult<Self> {
+        Ok(Self { seen })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        if let Note::Keep(n) = msg {
+            let _ = self.seen.send(n);
+        }
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn requests_failing_before_they_are_forwarded_are_skipped() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let (seen_tx, mut seen) = mpsc::unbounded_channel();
+    let notes = scope.spawn::<Notes>(seen_tx).await;
+
+    let (server, connector) = RpcServer::memory();
+    let server = tokio::spawn(server.serve(notes));
+    let client = RpcClient::<Note>::connect_memory(&connector).await.unwrap();
+
+    client.cast(Note::Reject).await.unwrap();
+    client.cast(Note::Keep(1)).await.unwrap();
+
+    let n = tokio::time::timeout(Duration::from_secs(1), seen.recv())
+        .await
+        .expect("connection stalled on the rejected request");
+    assert_eq!(n, Some(1));
+
+    server.abort();
+    scope.exit_and_wait().await;
+}
+
+// Fails every accept, counting how often it was asked to
+struct Failing(Arc<AtomicUsize>);
+
+#[async_trait]
+impl Listener for Failing {
+    type Conn = DuplexStream;
+
+    async fn accept(&mut self) -> io::Result<DuplexStream> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        Err(io::Error::other("too many open files"))
+    }
+}
+
+#[tokio::test]
+async fn failing_accepts_back_off() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let echo = scope.spawn::<Echo>(()).await;
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let server = RpcServer::new(Failing(attempts.clone()));
+    let _ = tokio::time::timeout(Duration::from_millis(200), server.serve(echo)).await;
+
+    // 5ms doubling each time fits 6 attempts into 200ms
+    assert!(attempts.load(Ordering::SeqCst) <= 10);
+
+    scope.exit_and_wait().await;
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorOutput, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext};
+
+struct Worker {
+    hang: bool,
+}
+
+#[async_trait]
+impl Actor for Worker {
+    // Whether `exit` never returns
+    type Args = bool;
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, hang: Self::Args) -> ActorResult<Self> {
+        Ok(Self { hang })
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        if self.hang {
+            std::future::pending().await
+        }
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn exit_all_does_not_wait_for_actors() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let quick = scope.spawn::<Worker>(false).await;
+    let _hang = scope.spawn::<Worker>(true).await;
+
+    tokio::time::timeout(Duration::from_millis(100), scope.exit_all())
+        .await
+        .expect("exit_all waited for the actors");
+
+    // Signalled even though the hanging actor would be stopped before it in order
+    let (id, output) = scope.next_finished().await.unwrap();
+    assert_eq!(id, quick.id());
+    assert!(matches!(output, ActorOutput::Exited(_)));
+
+    scope.abort_all();
+}
+
+#[tokio::test]
+async fn exit_and_wait_timeout_aborts_stragglers() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let quick = scope.spawn::<Worker>(false).await;
+    let hang = scope.spawn::<Worker>(true).await;
+
+    let report = tokio::time::timeout(
+        Duration::from_secs(1),
+        scope.exit_and_wait_timeout(Duration::from_millis(50)),
+    )
+    .await
+    .expect("exit_and_wait_timeout did not return");
+
+    assert_eq!(report.aborted, [hang.id()]);
+    assert_eq!(report.exited, [quick.id()]);
+    assert!(!report.is_clean());
+}
+
+#[tokio::test]
+async fn exit_and_wait_timeout_asks_every_actor_despite_a_hanging_one() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let mut quick = vec![];
+    for _ in 0..3 {
+        quick.push(scope.spawn::<Worker>(false).await.id());
+    }
+    // Stopped first
+    let hang = scope.spawn::<Worker>(true).await;
+
+    let mut report = scope
+        .exit_and_wait_timeout(Duration::from_millis(200))
+        .await;
+
+    report.exited.sort();
+    assert_eq!(report.exited, quick);
+    assert_eq!(report.aborted, [hang.id()]);
+}
@@ -0,0 +1,69 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use xacto::{
+    Actor, ActorError, ActorResult, ActorSelf, ExitReason, Restart, RestartStrategy, Scope,
+    ScopeContext, SupervisorMsg, SupervisorSpec, WorkerSpec,
+};
+
+struct Worker;
+
+#[async_trait]
+impl Actor for Worker {
+    // Which attempt at starting this is
+    type Args = usize;
+    type Msg = ();
+
+    async fn start(_: &ActorSelf<Self>, attempt: Self::Args) -> ActorResult<Self> {
+        if attempt == 0 {
+            return Err(ActorError::HandleClosed);
+        }
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn transient_child_failing_to_start_is_restarted() {
+    let mut scope = Scope::new(ScopeContext::new());
+
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let worker = WorkerSpec::<Worker>::new("w", {
+        let attempts = attempts.clone();
+        move || attempts.fetch_add(1, Ordering::SeqCst)
+    })
+    .restart(Restart::Transient);
+    let sup = scope
+        .start_tree(SupervisorSpec::new(RestartStrategy::OneForOne).child(worker))
+        .await;
+
+    let children = tokio::time::timeout(Duration::from_secs(1), async {
+        loop {
+            let children = sup.call(SupervisorMsg::WhichChildren).await.unwrap();
+            if children[0].1.is_some() {
+                return children;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("worker was not restarted");
+
+    assert_eq!(children[0].0, "w");
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+
+    scope.exit_and_wait().await;
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use xacto::{Actor, ActorOutput, ActorResult, ActorSelf, ExitReason, Scope, ScopeContext};
+
+struct Ticker;
+
+#[async_trait]
+impl Actor for Ticker {
+    type Args = Duration;
+    type Msg = ();
+
+    async fn start(this: &ActorSelf<Self>, period: Self::Args) -> ActorResult<Self> {
+        this.send_interval(period, || ());
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, _: Self::Msg) -> ActorResult {
+        Ok(())
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn zero_interval_panics_in_the_caller() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let _ticker = scope.spawn::<Ticker>(Duration::ZERO).await;
+
+    let (_, output) = scope.next_finished().await.unwrap();
+    assert!(matches!(output, ActorOutput::Panicked(_)));
+}
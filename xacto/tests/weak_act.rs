@@ -0,0 +1,60 @@
+use async_trait::async_trait;
+use xacto::{Actor, ActorOutput, ActorResult, ActorSelf, ExitReason, Reply, Scope, ScopeContext};
+
+#[derive(Debug)]
+enum Msg {
+    Ping(Reply<()>),
+}
+
+struct Session;
+
+#[async_trait]
+impl Actor for Session {
+    type Args = ();
+    type Msg = Msg;
+
+    async fn start(_: &ActorSelf<Self>, _: Self::Args) -> ActorResult<Self> {
+        Ok(Self)
+    }
+
+    async fn receive(&mut self, _: &ActorSelf<Self>, msg: Self::Msg) -> ActorResult {
+        match msg {
+            Msg::Ping(reply) => Ok(reply.send(())?),
+        }
+    }
+
+    async fn exit(&mut self, _: &ExitReason) -> ActorResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn upgrade_fails_after_last_act_is_dropped() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let session = scope.spawn::<Session>(()).await;
+    let weak = session.downgrade();
+
+    let upgraded = weak.upgrade().expect("upgrade with a live Act");
+    upgraded.call(Msg::Ping).await.unwrap();
+    drop(upgraded);
+
+    drop(session);
+    assert!(weak.upgrade().is_none());
+
+    scope.exit_and_wait().await;
+}
+
+#[tokio::test]
+async fn actor_with_only_weak_handles_stops() {
+    let mut scope = Scope::new(ScopeContext::new());
+    let session = scope.spawn::<Session>(()).await;
+    let id = session.id();
+    let weak = session.downgrade();
+
+    drop(session);
+
+    let (finished, output) = scope.next_finished().await.unwrap();
+    assert_eq!(finished, id);
+    assert!(matches!(output, ActorOutput::Exited(ExitReason::Normal)));
+    assert!(weak.upgrade().is_none());
+}